@@ -0,0 +1,61 @@
+//! Generates a test for every example fixture
+//!
+//! Fixtures live in `examples/day{N}/`: `{name}.txt` is the input and `star1.toml`/`star2.toml`
+//! map example names to expected answers, e.g. `1 = "11"`. Optional `params.toml` has a table of
+//! parameter overrides per example name.
+//!
+//! Only the example names are read here. The tests load answers and parameters through
+//! `runner::examples`, the same parser `--watch` uses.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+const EXAMPLES_DIR: &str = "examples";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={EXAMPLES_DIR}");
+
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join(EXAMPLES_DIR);
+    let mut days: Vec<(u8, _)> = fs::read_dir(&root)
+        .map(|dir| {
+            dir.filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let day = path
+                    .file_name()?
+                    .to_str()?
+                    .strip_prefix("day")?
+                    .parse()
+                    .ok()?;
+                Some((day, path))
+            })
+            .collect()
+        })
+        .unwrap_or_default();
+    days.sort();
+
+    let mut tests = String::new();
+    for (day, dir) in days {
+        for star in [1, 2] {
+            let expected_file = dir.join(format!("star{star}.toml"));
+            let Ok(content) = fs::read_to_string(&expected_file) else {
+                continue;
+            };
+            let expected: toml::Table = toml::from_str(&content)
+                .unwrap_or_else(|err| panic!("{}: {err}", expected_file.display()));
+            for name in expected.keys() {
+                let ident: String = name
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect();
+                writeln!(
+                    tests,
+                    "#[test]\nfn day{day}_star{star}_{ident}() {{\n    check({day}, {star}, {name:?});\n}}\n",
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
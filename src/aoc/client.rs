@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use tracing::{debug, instrument};
+use ureq::Agent;
+
+use crate::{
+    constants::USER_AGENT,
+    error::{AppError, Result},
+    solutions::Star,
+};
+
+use super::{submit::SubmitResponse, YEAR};
+
+/// Puzzle site client authenticated with a session cookie
+pub struct AocClient {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+        Self {
+            agent,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// `{base_url}/{year}/day/{day}{suffix}`
+    pub(crate) fn day_url(&self, day: u8, suffix: &str) -> String {
+        format!("{}/{YEAR}/day/{day}{suffix}", self.base_url)
+    }
+
+    pub(crate) fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    #[instrument(skip(self))]
+    pub fn fetch_input(&self, day: u8) -> Result<String> {
+        let url = self.day_url(day, "/input");
+        debug!(url = url, "Downloading input");
+        let mut response = self
+            .agent
+            .get(&url)
+            .header("Cookie", self.cookie())
+            .call()
+            .map_err(|err| transport_error(&url, err))?;
+        let status = response.status().as_u16();
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|err| transport_error(&url, err))?;
+        match status {
+            200 => Ok(body),
+            400 | 401 | 403 => Err(AppError::DataOpen(format!(
+                "Session token was rejected by {url} (HTTP {status}), it may have expired"
+            ))
+            .into()),
+            404 => Err(AppError::DataOpen(format!(
+                "Input of day {day} is not available at {url} (HTTP 404), the puzzle may be locked"
+            ))
+            .into()),
+            _ => Err(AppError::DataOpen(format!(
+                "Unexpected HTTP {status} from {url}: {}",
+                body.trim()
+            ))
+            .into()),
+        }
+    }
+
+    /// Posts the answer of a level and classifies the returned page
+    #[instrument(skip(self))]
+    pub fn submit_answer(&self, day: u8, star: Star, answer: &str) -> Result<SubmitResponse> {
+        let url = self.day_url(day, "/answer");
+        debug!(url = url, "Submitting answer");
+        let level = (star as u8).to_string();
+        let mut response = self
+            .agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .map_err(|err| transport_error(&url, err))?;
+        let status = response.status().as_u16();
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|err| transport_error(&url, err))?;
+        match status {
+            200 => Ok(SubmitResponse::parse(&body)),
+            400 | 401 | 403 => Err(AppError::DataOpen(format!(
+                "Session token was rejected by {url} (HTTP {status}), it may have expired"
+            ))
+            .into()),
+            _ => Err(AppError::DataOpen(format!(
+                "Unexpected HTTP {status} from {url}: {}",
+                body.trim()
+            ))
+            .into()),
+        }
+    }
+}
+
+pub(crate) fn transport_error(url: &str, err: ureq::Error) -> AppError {
+    AppError::DataOpen(format!("Request to {url} failed: {err}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::test_server::TestServer, *};
+
+    #[test]
+    fn fetch_input_sends_session() {
+        let server = TestServer::start(vec![(200, "125 17\n".to_string())]);
+        let client = AocClient::new(server.url(), "token");
+        assert_eq!(client.fetch_input(11).unwrap(), "125 17\n");
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /2024/day/11/input "));
+        assert!(requests[0].contains("session=token"));
+    }
+
+    #[test]
+    fn fetch_input_maps_auth_failure() {
+        let server = TestServer::start(vec![(400, "Puzzle inputs differ by user.".to_string())]);
+        let client = AocClient::new(server.url(), "expired");
+        let err = client.fetch_input(1).unwrap_err().to_string();
+        assert!(err.contains("Session token was rejected"), "{err}");
+    }
+
+    #[test]
+    fn fetch_input_maps_locked_day() {
+        let server = TestServer::start(vec![(404, "Not Found".to_string())]);
+        let client = AocClient::new(server.url(), "token");
+        let err = client.fetch_input(25).unwrap_err().to_string();
+        assert!(err.contains("not available"), "{err}");
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use tracing::{debug, instrument};
+
+use crate::error::{AppError, Result};
+
+pub mod client;
+pub mod submit;
+
+#[cfg(test)]
+mod test_server;
+
+pub use client::AocClient;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2024;
+
+#[derive(clap::Args, Clone)]
+pub struct ClientArgs {
+    /// Puzzle site base URL
+    #[clap(
+        long,
+        env = "ADVENT_2024_BASE_URL",
+        default_value = DEFAULT_BASE_URL,
+        global = true
+    )]
+    pub base_url: String,
+
+    /// Session cookie of the logged in puzzle site user
+    #[clap(long, env = "AOC_SESSION", hide_env_values = true, global = true)]
+    pub session: Option<String>,
+
+    /// File with the session cookie, used when `--session` is not set
+    #[clap(long, default_value = ".aoc-session", global = true)]
+    pub session_file: PathBuf,
+}
+
+/// Redacts the session cookie, arguments are traced at startup
+impl fmt::Debug for ClientArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientArgs")
+            .field("base_url", &self.base_url)
+            .field("session", &self.session.as_ref().map(|_| "***"))
+            .field("session_file", &self.session_file)
+            .finish()
+    }
+}
+
+impl ClientArgs {
+    pub fn session(&self) -> Result<String> {
+        if let Some(session) = &self.session {
+            return Ok(session.trim().to_string());
+        }
+        match fs::read_to_string(&self.session_file) {
+            Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+            _ => Err(AppError::DataOpen(format!(
+                "Session token not found, set AOC_SESSION or write it to {}",
+                self.session_file.display()
+            ))
+            .into()),
+        }
+    }
+
+    pub fn client(&self) -> Result<AocClient> {
+        Ok(AocClient::new(&self.base_url, self.session()?))
+    }
+}
+
+/// Downloads the day's input into `path` unless it is already there
+///
+/// Returns `false` when the existing file was kept.
+#[instrument(skip(client))]
+pub fn fetch_input_file(client: &AocClient, day: u8, path: &Path, force: bool) -> Result<bool> {
+    if path.exists() && !force {
+        debug!("Input already cached");
+        return Ok(false);
+    }
+    let input = client.fetch_input(day)?;
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)
+            .map_err(|err| AppError::DataOpen(format!("{}: {err}", dir.display())))?;
+    }
+    fs::write(path, input)
+        .map_err(|err| AppError::DataOpen(format!("{}: {err}", path.display())))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{test_server::TestServer, *};
+
+    #[test]
+    fn fetch_input_file_keeps_cached_file() {
+        let server = TestServer::start(vec![(200, "1 2\n".to_string())]);
+        let client = AocClient::new(server.url(), "token");
+        let dir = std::env::temp_dir().join(format!("advent_2024_fetch_{}", std::process::id()));
+        let path = dir.join("day1.txt");
+
+        assert!(fetch_input_file(&client, 1, &path, false).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n");
+        assert!(!fetch_input_file(&client, 1, &path, false).unwrap());
+        assert_eq!(server.requests().len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn debug_redacts_session() {
+        let args = ClientArgs {
+            base_url: DEFAULT_BASE_URL.into(),
+            session: Some("SECRET123".into()),
+            session_file: ".aoc-session".into(),
+        };
+        let debug = format!("{args:?}");
+        assert!(!debug.contains("SECRET123"), "{debug}");
+        assert!(debug.contains(r#"session: Some("***")"#), "{debug}");
+    }
+}
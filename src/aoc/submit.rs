@@ -0,0 +1,349 @@
+use std::{
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::{
+    error::{AppError, Result},
+    solutions::Star,
+};
+
+use super::AocClient;
+
+/// Submissions log file name inside the inputs directory
+pub const SUBMISSIONS_FILE: &str = "submissions.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited,
+    /// Level is already completed
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    fn is_rejection(&self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too high",
+            Self::TooLow => "too low",
+            Self::Wrong => "wrong",
+            Self::RateLimited => "rate limited",
+            Self::AlreadySolved => "already solved",
+            Self::Unknown => "unknown response",
+        };
+        f.write_str(verdict)
+    }
+}
+
+/// Classified answer page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmitResponse {
+    pub verdict: Verdict,
+    /// Time to wait before the next submission
+    pub wait: Option<Duration>,
+    /// Text of the response article
+    pub message: String,
+}
+
+impl SubmitResponse {
+    pub fn parse(html: &str) -> Self {
+        let message = article_text(html);
+        let verdict = if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if message.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        };
+        let wait = parse_wait(&message);
+        Self {
+            verdict,
+            wait,
+            message,
+        }
+    }
+}
+
+/// Text inside `<article>`, or the whole page, without tags
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+    let mut text = String::with_capacity(article.len());
+    let mut in_tag = false;
+    for ch in article.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Understands "You have 1m 23s left to wait" and "wait one minute"/"wait 5 minutes"
+fn parse_wait(message: &str) -> Option<Duration> {
+    if let Some((before, _)) = message.split_once(" left to wait") {
+        let secs = before
+            .rsplit_once("You have ")?
+            .1
+            .split_whitespace()
+            .map(|part| {
+                let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+                let n: u64 = n.parse().ok()?;
+                match unit {
+                    "h" => Some(n * 3600),
+                    "m" => Some(n * 60),
+                    "s" => Some(n),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(secs));
+    }
+    let (_, after) = message.split_once("wait ")?;
+    let mut words = after.split_whitespace();
+    let n = match words.next()? {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+    words
+        .next()
+        .filter(|unit| unit.starts_with("minute"))
+        .map(|_| Duration::from_secs(n * 60))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub star: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Unix time of the submission
+    pub at: u64,
+}
+
+/// Local history of submissions, so known wrong answers are never sent twice
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SubmissionLog {
+    /// Unix time before which nothing is submitted
+    #[serde(default)]
+    pub wait_until: u64,
+    #[serde(default, rename = "submission")]
+    pub submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Loads submissions file, missing file is an empty log
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(AppError::DataOpen(format!("{}: {err}", path.display())).into()),
+        };
+        toml::from_str(&content)
+            .map_err(|err| AppError::DataParse(format!("{}: {err}", path.display()).into()).into())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string(self).map_err(|err| AppError::Other(err.to_string()))?;
+        fs::write(path, content)
+            .map_err(|err| AppError::DataOpen(format!("{}: {err}", path.display())).into())
+    }
+
+    /// Refuses answers that are known to be wrong, already solved levels and cooldowns
+    pub fn check(&self, day: u8, star: Star, answer: &str, now: u64) -> Result<(), AppError> {
+        if now < self.wait_until {
+            return Err(AppError::SubmitRefused(format!(
+                "Submission cooldown, {}s left to wait",
+                self.wait_until - now
+            )));
+        }
+        let previous = self
+            .submissions
+            .iter()
+            .filter(|s| s.day == day && s.star == star as u8);
+        if let Some(correct) = previous.clone().find(|s| s.verdict == Verdict::Correct) {
+            return Err(AppError::SubmitRefused(format!(
+                "Day {day} star {star} is already solved with {}",
+                correct.answer
+            )));
+        }
+        let value: Option<i128> = answer.parse().ok();
+        // Unknown or rate limited submissions of the same answer must not hide a rejection
+        let rejected = previous.filter(|s| s.verdict.is_rejection()).find(|s| {
+            let bound: Option<i128> = s.answer.parse().ok();
+            s.answer == answer
+                || match (s.verdict, value.zip(bound)) {
+                    (Verdict::TooHigh, Some((value, bound))) => value >= bound,
+                    (Verdict::TooLow, Some((value, bound))) => value <= bound,
+                    _ => false,
+                }
+        });
+        match rejected {
+            Some(s) => Err(AppError::SubmitRefused(format!(
+                "Answer {answer} is known to be wrong, {} was {}",
+                s.answer, s.verdict
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        day: u8,
+        star: Star,
+        answer: &str,
+        response: &SubmitResponse,
+        now: u64,
+    ) {
+        if let Some(wait) = response.wait {
+            self.wait_until = self.wait_until.max(now + wait.as_secs());
+        }
+        if response.verdict != Verdict::RateLimited {
+            self.submissions.push(Submission {
+                day,
+                star: star as u8,
+                answer: answer.to_string(),
+                verdict: response.verdict,
+                at: now,
+            });
+        }
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Checks the log, posts the answer and records the response
+#[instrument(skip(client, log))]
+pub fn submit(
+    client: &AocClient,
+    log: &mut SubmissionLog,
+    day: u8,
+    star: Star,
+    answer: &str,
+    now: u64,
+) -> Result<SubmitResponse> {
+    log.check(day, star, answer, now)?;
+    let response = client.submit_answer(day, star, answer)?;
+    debug!(response = ?response, "Answer submitted");
+    log.record(day, star, answer, &response, now);
+    Ok(response)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::test_server::TestServer, *};
+    use crate::error::ErrorKind;
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main><article><p>{article}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn parse_correct() {
+        let html = page("That's the right answer! You are <span>one gold star</span> closer.");
+        assert_eq!(SubmitResponse::parse(&html).verdict, Verdict::Correct);
+    }
+
+    #[test]
+    fn parse_too_high() {
+        let html = page("That's not the right answer; your answer is too high. Please wait one minute before trying again.");
+        let response = SubmitResponse::parse(&html);
+        assert_eq!(response.verdict, Verdict::TooHigh);
+        assert_eq!(response.wait, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn parse_wrong() {
+        let html = page("That's not the right answer. If you're stuck, please wait 5 minutes before trying again.");
+        let response = SubmitResponse::parse(&html);
+        assert_eq!(response.verdict, Verdict::Wrong);
+        assert_eq!(response.wait, Some(Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn parse_rate_limited() {
+        let html = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.");
+        let response = SubmitResponse::parse(&html);
+        assert_eq!(response.verdict, Verdict::RateLimited);
+        assert_eq!(response.wait, Some(Duration::from_secs(83)));
+    }
+
+    #[test]
+    fn log_refuses_known_wrong_and_cooldown() {
+        let server = TestServer::start(vec![(
+            200,
+            page("That's not the right answer; your answer is too low. Please wait one minute before trying again."),
+        )]);
+        let client = AocClient::new(server.url(), "token");
+        let mut log = SubmissionLog::default();
+
+        let response = submit(&client, &mut log, 7, Star::One, "100", 1000).unwrap();
+        assert_eq!(response.verdict, Verdict::TooLow);
+        assert!(server.requests()[0].contains("level=1&answer=100"));
+
+        assert!(log.check(7, Star::One, "200", 1030).is_err());
+        assert!(log.check(7, Star::One, "100", 2000).is_err());
+        assert!(log.check(7, Star::One, "50", 2000).is_err());
+        assert!(log.check(7, Star::One, "200", 2000).is_ok());
+        assert!(log.check(7, Star::Two, "100", 2000).is_ok());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn unknown_verdict_does_not_hide_wrong_one() {
+        let submission = |answer: &str, verdict, at| Submission {
+            day: 3,
+            star: 1,
+            answer: answer.into(),
+            verdict,
+            at,
+        };
+        let log = SubmissionLog {
+            wait_until: 0,
+            submissions: vec![
+                submission("42", Verdict::Unknown, 100),
+                submission("42", Verdict::Wrong, 200),
+            ],
+        };
+        let err = log.check(3, Star::One, "42", 1000).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SubmitRefused);
+        assert!(err.to_string().contains("known to be wrong"), "{err}");
+    }
+}
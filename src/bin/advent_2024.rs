@@ -1,115 +1,254 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
 use advent_2024::{
+    aoc::{
+        self,
+        submit::{self, SubmissionLog, Verdict},
+    },
     cli::{Args, Command},
-    constants, error, solutions, utils,
+    constants,
+    error::{self, AppError, ErrorKind},
+    runner::{
+        self,
+        answers::{self, AnswerBook},
+        examples::EXAMPLES_DIR,
+        memory::format_bytes,
+        output::{write_error, write_reports, OutputFormat},
+        RunReport,
+    },
+    scaffold,
+    solutions::{self, Params, Star},
+    utils,
 };
 use clap::Parser;
-use tracing::{debug, trace};
+use std::{path::Path, process::ExitCode};
+use tracing::{debug, info, trace};
 
-fn main() -> error::Result<()> {
-    utils::color_eyre::setup()?;
+/// Exit code of every [`AppError`] is listed on [`error::ErrorKind`]
+fn main() -> ExitCode {
+    if let Err(err) = utils::color_eyre::setup() {
+        eprintln!("Error: {err:?}");
+        return ExitCode::FAILURE;
+    }
 
     let args = Args::parse();
-    args.instrumentation.setup(&[constants::PACKAGE_NAME])?;
-    trace!(args = ?args, "Arguments parsed, Instrumentation setup");
+    if let Err(err) = args.validate() {
+        err.exit();
+    }
+    match run(&args) {
+        Ok(code) => code,
+        Err(err) => {
+            if let Err(write_err) = write_error(std::io::stdout().lock(), args.output, &err) {
+                eprintln!("Error: {write_err:?}");
+            }
+            eprintln!("Error: {err:?}");
+            ExitCode::from(err.kind().exit_code())
+        }
+    }
+}
+
+/// Exit code of the first failed run, reports already carry the errors
+///
+/// Days without a solution are not failures, `all` lists every day of the calendar.
+fn reports_exit_code(reports: &[RunReport]) -> ExitCode {
+    reports
+        .iter()
+        .filter_map(|report| report.answer.as_ref().err())
+        .find(|err| err.kind != ErrorKind::NotImplemented)
+        .map_or(ExitCode::SUCCESS, |err| {
+            ExitCode::from(err.kind.exit_code())
+        })
+}
 
-    let data_file = match File::open(args.data_file) {
-        Ok(file) => file,
-        Err(err) => return Err(advent_2024::error::AppError::DataOpen(err.to_string()).into()),
+fn run(args: &Args) -> error::Result<ExitCode> {
+    // Benchmarks run without any subscriber, so tracing stays out of the measurements
+    let _guard = match args.command {
+        Command::Bench { .. } => None,
+        _ => Some(args.instrumentation.setup(&[constants::PACKAGE_NAME])?),
     };
-    let data_reader = BufReader::new(&data_file);
-    trace!(file = ?data_file, reader = ?data_reader, "Data file reader initialized");
+    trace!(args = ?args, "Arguments parsed, Instrumentation setup");
+    runner::set_raw_input(args.raw);
+    if let Some(threads) = args.threads() {
+        runner::set_threads(threads)?;
+        debug!(threads = threads, "Thread pool configured");
+    }
 
-    match args.command {
-        Command::Day1 => {
-            debug!("Day1 solution requested");
-            solutions::day1::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day2 => {
-            debug!("Day2 solution requested");
-            solutions::day2::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day3 => {
-            debug!("Day3 solution requested");
-            solutions::day3::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day4 => {
-            debug!("Day4 solution requested");
-            solutions::day4::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day5 => {
-            debug!("Day5 solution requested");
-            solutions::day5::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day6 => {
-            debug!("Day6 solution requested");
-            solutions::day6::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day7 => {
-            debug!("Day7 solution requested");
-            solutions::day7::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day8 => {
-            debug!("Day8 solution requested");
-            solutions::day8::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day9 => {
-            debug!("Day9 solution requested");
-            solutions::day9::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day10 => {
-            debug!("Day10 solution requested");
-            solutions::day10::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day11 => {
-            debug!("Day11 solution requested");
-            solutions::day11::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day12 => {
-            debug!("Day12 solution requested");
-            solutions::day12::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day13 => {
-            debug!("Day13 solution requested");
-            solutions::day13::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day14 => {
-            debug!("Day14 solution requested");
-            solutions::day14::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day15 => {
-            debug!("Day15 solution requested");
-            solutions::day15::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day16 => {
-            debug!("Day16 solution requested");
-            solutions::day16::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day17 => {
-            debug!("Day17 solution requested");
-            solutions::day17::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day18 => {
-            debug!("Day18 solution requested");
-            solutions::day18::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day19 => {
-            debug!("Day19 solution requested");
-            solutions::day19::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day20 => {
-            debug!("Day20 solution requested");
-            solutions::day20::solve(args.second_star, data_reader.lines())?;
-        }
-        Command::Day21 => {
-            debug!("Day21 solution requested");
-            solutions::day21::solve(args.second_star, data_reader.lines())?;
+    match &args.command {
+        Command::Day {
+            day,
+            data_file,
+            watch,
+            params,
+        } => {
+            let solution = solutions::find(*day).ok_or(AppError::NotImplemented(*day))?;
+            debug!(day = day, title = solution.title(), "Solution requested");
+            let params = Params::resolve(solution.params(), params)?;
+            debug!(params = ?params, "Parameters resolved");
+
+            let data_file = args.data_file(*day, data_file);
+            if *watch {
+                runner::watch::watch(
+                    solution,
+                    args.stars(),
+                    &data_file,
+                    &params,
+                    args.timeout,
+                    Path::new(EXAMPLES_DIR),
+                )?;
+                return Ok(ExitCode::SUCCESS);
+            }
+            if args.output != OutputFormat::Text {
+                let reports =
+                    runner::run(solution, args.stars(), &data_file, &params, args.timeout);
+                write_reports(std::io::stdout().lock(), args.output, &reports)?;
+                return Ok(reports_exit_code(&reports));
+            }
+            let input = runner::parse(solution, &data_file)?;
+            if args.both {
+                println!("Parse: {:.2?}", input.time);
+            }
+            for &star in args.stars() {
+                let solved = runner::solve(solution, star, &input, &params, args.timeout);
+                let answer = solved.answer?;
+                if args.both {
+                    match solved.memory {
+                        Some(memory) => println!(
+                            "Star {star}: {answer} ({:.2?}, peak {}, {} in {} allocations)",
+                            solved.time,
+                            format_bytes(memory.peak_bytes),
+                            format_bytes(memory.allocated_bytes),
+                            memory.allocations
+                        ),
+                        None => println!("Star {star}: {answer} ({:.2?})", solved.time),
+                    }
+                } else {
+                    println!("{answer}");
+                }
+            }
+        }
+        Command::Record { day, data_file } => {
+            let solution = solutions::find(*day).ok_or(AppError::NotImplemented(*day))?;
+            let data_file = args.data_file(*day, data_file);
+            if data_file.as_os_str() == runner::STDIN_PATH {
+                return Err(AppError::DataOpen("Cannot record answers for stdin".into()).into());
+            }
+
+            let answers_file = args.answers_file();
+            let mut book = AnswerBook::load(&answers_file)?;
+            let input = runner::parse(solution, &data_file)?;
+            let params = Params::defaults(solution.params());
+            for &star in args.stars() {
+                let answer = runner::solve(solution, star, &input, &params, args.timeout).answer?;
+                println!("Star {star}: {answer}");
+                book.record(*day, star, &data_file, &answer);
+            }
+            book.save(&answers_file)?;
+            info!(answers_file = ?answers_file, "Answers recorded");
+        }
+        Command::Verify => {
+            let book = AnswerBook::load(&args.answers_file())?;
+            let verifications = book.verify(args.timeout);
+            let mut out = std::io::stdout().lock();
+            for verification in verifications.iter() {
+                verification.write_diff(&mut out)?;
+            }
+            answers::check(&verifications)?;
+        }
+        Command::Bench {
+            day,
+            data_file,
+            iterations,
+            warmup,
+            format,
+            compare,
+            params,
+        } => {
+            let solution = solutions::find(*day).ok_or(AppError::NotImplemented(*day))?;
+            let params = Params::resolve(solution.params(), params)?;
+            let data_file = args.data_file(*day, data_file);
+            if *compare {
+                let comparison = runner::bench::compare(
+                    solution,
+                    args.stars(),
+                    &data_file,
+                    &params,
+                    *iterations,
+                    *warmup,
+                )?;
+                comparison.write(std::io::stdout().lock(), *format)?;
+                return Ok(ExitCode::SUCCESS);
+            }
+            let report = runner::bench::bench(
+                solution,
+                args.stars(),
+                &data_file,
+                &params,
+                *iterations,
+                *warmup,
+            )?;
+            report.write(std::io::stdout().lock(), *format)?;
+        }
+        Command::Fetch { day, force } => {
+            let path = runner::default_input(&args.inputs_dir, *day);
+            if aoc::fetch_input_file(&args.client.client()?, *day, &path, *force)? {
+                info!(path = ?path, "Input downloaded");
+            } else {
+                info!(path = ?path, "Input already exists, use --force to download again");
+            }
+        }
+        Command::Submit { day, answer } => {
+            let star = if args.second_star {
+                Star::Two
+            } else {
+                Star::One
+            };
+            let data_file = runner::default_input(&args.inputs_dir, *day);
+            let answer = match answer {
+                Some(answer) => answer.trim().to_string(),
+                None => {
+                    let solution = solutions::find(*day).ok_or(AppError::NotImplemented(*day))?;
+                    let input = runner::parse(solution, &data_file)?;
+                    let params = Params::defaults(solution.params());
+                    runner::solve(solution, star, &input, &params, args.timeout)
+                        .answer?
+                        .to_string()
+                }
+            };
+
+            let submissions_file = args.submissions_file();
+            let mut log = SubmissionLog::load(&submissions_file)?;
+            let client = args.client.client()?;
+            let response =
+                submit::submit(&client, &mut log, *day, star, &answer, submit::unix_now());
+            log.save(&submissions_file)?;
+            let response = response?;
+            println!("Day {day} star {star}: {answer} is {}", response.verdict);
+            if let Some(wait) = response.wait {
+                println!("Wait {}s before the next submission", wait.as_secs());
+            }
+            if response.verdict == Verdict::Unknown {
+                println!("{}", response.message);
+            }
+        }
+        Command::NewDay { day, title, root } => {
+            let title = title.clone().unwrap_or_else(|| format!("Day {day}"));
+            for path in scaffold::new_day(root, *day, &title)? {
+                println!("{}", path.display());
+            }
+        }
+        Command::All => {
+            let reports = runner::run_all(&args.inputs_dir, args.timeout);
+            write_reports(std::io::stdout().lock(), args.output, &reports)?;
+            return Ok(reports_exit_code(&reports));
+        }
+        Command::List => {
+            for solution in solutions::REGISTRY {
+                println!("{:>2} {}", solution.day(), solution.title());
+                for spec in solution.params() {
+                    let param = format!("{}={}", spec.name, spec.default);
+                    println!("     --param {param:<24} {}", spec.help);
+                }
+            }
         }
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
@@ -1,6 +1,11 @@
-use crate::instrument::instrumentation::Instrumentation;
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use crate::{
+    aoc::{submit::SUBMISSIONS_FILE, ClientArgs},
+    instrument::instrumentation::Instrumentation,
+    runner::{self, bench::BenchFormat, output::OutputFormat},
+    solutions::{params::parse_key_value, Star},
+};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
+use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
 
 /// Program to solve 2024 advent of code
 #[derive(Debug, Parser)]
@@ -9,60 +14,268 @@ pub struct Args {
     #[clap(flatten)]
     pub instrumentation: Instrumentation,
 
-    /// Sets a data file
-    #[arg(value_name = "DATA_FILE")]
-    pub data_file: PathBuf,
+    #[clap(flatten)]
+    pub client: ClientArgs,
 
     /// Should solve second star problem
     #[arg(long, short, global = true)]
     pub second_star: bool,
 
+    /// Should solve both star problems with a single parse
+    #[arg(long, short, global = true, conflicts_with = "second_star")]
+    pub both: bool,
+
+    /// Directory with `day{N}.txt` puzzle inputs
+    #[arg(
+        long,
+        env = "ADVENT_2024_INPUTS",
+        default_value = "inputs",
+        global = true
+    )]
+    pub inputs_dir: PathBuf,
+
+    /// Format of the results written to stdout, json and csv are only supported by `day` and `all`
+    #[arg(long, short, default_value = "text", global = true)]
+    pub output: OutputFormat,
+
+    /// Stop waiting for a solution after this long, e.g. `30s`, `500ms` or `2m`, parsing the
+    /// input is not covered and bench ignores it
+    #[arg(long, value_parser = parse_duration, global = true)]
+    pub timeout: Option<Duration>,
+
+    /// Size of the rayon pool used by parallel solutions [default: number of CPUs]
+    #[arg(long, global = true)]
+    pub threads: Option<NonZeroUsize>,
+
+    /// Run parallel solutions on a single thread, so traces come out in a deterministic order
+    #[arg(long, global = true, conflicts_with = "threads")]
+    pub sequential: bool,
+
+    /// Keep BOM, CRLF, tabs, trailing whitespace and trailing blank lines of the input
+    #[arg(long, global = true)]
+    pub raw: bool,
+
     #[command(subcommand)]
     pub command: Command,
 }
 
+impl Args {
+    /// Rejects `--output` formats that the command cannot write, so stdout stays parseable
+    pub fn validate(&self) -> Result<(), clap::Error> {
+        let writes_reports = matches!(
+            self.command,
+            Command::Day { watch: false, .. } | Command::All
+        );
+        if self.output == OutputFormat::Text || writes_reports {
+            return Ok(());
+        }
+        Err(Self::command().error(
+            ErrorKind::ArgumentConflict,
+            "`--output json` and `--output csv` are only supported by `day` without `--watch` and `all`",
+        ))
+    }
+
+    /// Stars selected by the flags
+    pub fn stars(&self) -> &'static [Star] {
+        if self.both {
+            &Star::ALL
+        } else if self.second_star {
+            &[Star::Two]
+        } else {
+            &[Star::One]
+        }
+    }
+
+    /// Rayon pool size selected by the flags, `None` keeps the default
+    pub fn threads(&self) -> Option<usize> {
+        if self.sequential {
+            Some(1)
+        } else {
+            self.threads.map(NonZeroUsize::get)
+        }
+    }
+
+    /// Answers file inside the inputs directory
+    pub fn answers_file(&self) -> PathBuf {
+        self.inputs_dir.join(runner::answers::ANSWERS_FILE)
+    }
+
+    /// Submissions log inside the inputs directory
+    pub fn submissions_file(&self) -> PathBuf {
+        self.inputs_dir.join(SUBMISSIONS_FILE)
+    }
+
+    /// Given data file or the day's default input
+    pub fn data_file(&self, day: u8, data_file: &Option<PathBuf>) -> PathBuf {
+        data_file
+            .clone()
+            .unwrap_or_else(|| runner::default_input(&self.inputs_dir, day))
+    }
+}
+
+/// Parses a duration with an `ms`, `s` or `m` suffix, plain numbers are seconds
+fn parse_duration(arg: &str) -> Result<Duration, String> {
+    let arg = arg.trim();
+    let (number, unit) = match arg.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => arg.split_at(i),
+        None => (arg, "s"),
+    };
+    let number: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("expected a duration like `30s`, got `{arg}`"))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "s" => number,
+        "m" => number * 60.0,
+        _ => return Err(format!("unknown unit `{unit}`, expected ms, s or m")),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|err| format!("`{arg}`: {err}"))
+}
+
 #[derive(Debug, Subcommand, Clone)]
 pub enum Command {
-    /// Day 1 solution [https://adventofcode.com/2024/day/1]
-    Day1,
-    /// Day 2 solution [https://adventofcode.com/2024/day/2]
-    Day2,
-    /// Day 3 solution [https://adventofcode.com/2024/day/3]
-    Day3,
-    /// Day 4 solution [https://adventofcode.com/2024/day/4]
-    Day4,
-    /// Day 5 solution [https://adventofcode.com/2024/day/5]
-    Day5,
-    /// Day 6 solution [https://adventofcode.com/2024/day/6]
-    Day6,
-    /// Day 7 solution [https://adventofcode.com/2024/day/7]
-    Day7,
-    /// Day 8 solution [https://adventofcode.com/2024/day/8]
-    Day8,
-    /// Day 9 solution [https://adventofcode.com/2024/day/9]
-    Day9,
-    /// Day 10 solution [https://adventofcode.com/2024/day/10]
-    Day10,
-    /// Day 12 solution [https://adventofcode.com/2024/day/11]
-    Day11,
-    /// Day 12 solution [https://adventofcode.com/2024/day/12]
-    Day12,
-    /// Day 13 solution [https://adventofcode.com/2024/day/13]
-    Day13,
-    /// Day 14 solution [https://adventofcode.com/2024/day/14]
-    Day14,
-    /// Day 15 solution [https://adventofcode.com/2024/day/15]
-    Day15,
-    /// Day 16 solution [https://adventofcode.com/2024/day/16]
-    Day16,
-    /// Day 17 solution [https://adventofcode.com/2024/day/17]
-    Day17,
-    /// Day 18 solution [https://adventofcode.com/2024/day/18]
-    Day18,
-    /// Day 19 solution [https://adventofcode.com/2024/day/19]
-    Day19,
-    /// Day 20 solution [https://adventofcode.com/2024/day/20]
-    Day20,
-    /// Day 21 solution [https://adventofcode.com/2024/day/21]
-    Day21,
+    /// Solve given day [https://adventofcode.com/2024/day/{DAY}]
+    Day {
+        /// Day of the advent calendar
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Sets a data file, `-` reads stdin [default: <INPUTS_DIR>/day{DAY}.txt]
+        #[arg(value_name = "DATA_FILE")]
+        data_file: Option<PathBuf>,
+
+        /// Re-run whenever the data file or the day's examples change
+        #[arg(long, short)]
+        watch: bool,
+
+        /// Overrides a puzzle parameter, see `list` for the day's parameters
+        #[arg(long = "param", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+        params: Vec<(String, String)>,
+    },
+    /// Solve given day and record the answers as known good
+    Record {
+        /// Day of the advent calendar
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Sets a data file [default: <INPUTS_DIR>/day{DAY}.txt]
+        #[arg(value_name = "DATA_FILE")]
+        data_file: Option<PathBuf>,
+    },
+    /// Re-run every recorded answer and report the ones that changed
+    Verify,
+    /// Measure parse and solve times of given day with logging disabled
+    Bench {
+        /// Day of the advent calendar
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Sets a data file, `-` reads stdin [default: <INPUTS_DIR>/day{DAY}.txt]
+        #[arg(value_name = "DATA_FILE")]
+        data_file: Option<PathBuf>,
+
+        /// Number of measured runs
+        #[arg(long, short = 'n', default_value_t = 10)]
+        iterations: usize,
+
+        /// Number of runs before measuring
+        #[arg(long, short, default_value_t = 3)]
+        warmup: usize,
+
+        /// Report format
+        #[arg(long, short, default_value = "table")]
+        format: BenchFormat,
+
+        /// Also run on a single thread and report the speedup of the `--threads` pool
+        #[arg(long)]
+        compare: bool,
+
+        /// Overrides a puzzle parameter, see `list` for the day's parameters
+        #[arg(long = "param", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+        params: Vec<(String, String)>,
+    },
+    /// Download the day's puzzle input into <INPUTS_DIR>/day{DAY}.txt
+    Fetch {
+        /// Day of the advent calendar
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Download even if the input file already exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Submit an answer of given day, the second star level is selected by `-s`
+    Submit {
+        /// Day of the advent calendar
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Answer to submit [default: solve <INPUTS_DIR>/day{DAY}.txt]
+        answer: Option<String>,
+    },
+    /// Create `src/solutions/day{DAY}.rs` from the template and register it
+    NewDay {
+        /// Day of the advent calendar
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Puzzle title [default: Day {DAY}]
+        title: Option<String>,
+
+        /// Crate root directory
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Solve both stars of every day with default inputs and print a summary table
+    All,
+    /// List implemented days with their parameters
+    List,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::instrument::logger::Logger;
+
+    use super::*;
+
+    fn validate(args: &[&str]) -> Result<(), clap::Error> {
+        Args::try_parse_from([&["advent_2024"], args].concat())?.validate()
+    }
+
+    #[test]
+    fn output_format_only_for_reports() {
+        assert!(validate(&["-o", "json", "day", "1"]).is_ok());
+        assert!(validate(&["all", "--output", "csv"]).is_ok());
+        assert!(validate(&["list"]).is_ok());
+        for args in [
+            &["-o", "json", "list"][..],
+            &["-o", "csv", "verify"],
+            &["-o", "json", "day", "1", "--watch"],
+        ] {
+            let err = validate(args).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ArgumentConflict, "{args:?}");
+        }
+    }
+
+    #[test]
+    fn log_file_logger_only_text() {
+        assert!(validate(&["--log-file-logger", "json", "list"]).is_ok());
+        let err = validate(&["--log-file-logger", "folded", "list"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+
+        let file_logger = |args: &[&str]| {
+            let args = Args::try_parse_from([&["advent_2024"], args].concat()).unwrap();
+            args.instrumentation.file_logger()
+        };
+        assert_eq!(file_logger(&["--logger", "pretty", "list"]), Logger::Pretty);
+        assert_eq!(
+            file_logger(&["--logger", "chrome-trace", "--log-file", "run.log", "list"]),
+            Logger::Compact
+        );
+        assert_eq!(
+            file_logger(&["--logger", "folded", "--log-file-logger", "json", "list"]),
+            Logger::Json
+        );
+    }
 }
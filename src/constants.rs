@@ -1 +1,7 @@
 pub const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
+pub const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/qxuken/advent-2024)"
+);
@@ -1,4 +1,7 @@
-use std::error::Error;
+use std::{error::Error, fmt, ops::Range};
+
+use color_eyre::{Section, SectionExt};
+use serde::Serialize;
 use thiserror::Error;
 
 pub type Result<T, E = Report> = color_eyre::Result<T, E>;
@@ -10,6 +13,37 @@ impl std::fmt::Debug for Report {
     }
 }
 
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Report {
+    /// Category of the error, [`ErrorKind::Internal`] for errors that are not an [`AppError`]
+    pub fn kind(&self) -> ErrorKind {
+        self.0
+            .downcast_ref::<AppError>()
+            .map_or(ErrorKind::Internal, AppError::kind)
+    }
+
+    /// Fills in the day of an [`AppError::DataParse`] and adds its source snippet as a section
+    #[track_caller]
+    pub fn with_parse_context(self, day: u8) -> Self {
+        let Some(AppError::DataParse(err)) = self.0.downcast_ref::<AppError>() else {
+            return self;
+        };
+        let mut err = err.clone();
+        err.day.get_or_insert(day);
+        let snippet = err.snippet();
+        let report = color_eyre::Report::new(AppError::DataParse(err));
+        Self(match snippet {
+            Some(snippet) => report.section(snippet.header("Input:")),
+            None => report,
+        })
+    }
+}
+
 impl<E> From<E> for Report
 where
     E: Into<color_eyre::Report>,
@@ -25,17 +59,248 @@ pub enum AppError {
     DataOpen(String),
 
     #[error("Data File Parsing Error: {0}")]
-    DataParse(String),
+    DataParse(ParseError),
 
     #[error("Error during execution: {0}")]
     LogicalError(String),
 
+    #[error("Day {0} is not implemented")]
+    NotImplemented(u8),
+
+    #[error("Panicked at {location}: {message}")]
+    Panic { message: String, location: String },
+
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    #[error("Invalid parameter: {0}")]
+    InvalidParam(String),
+
+    #[error("Answer mismatch: {0}")]
+    AnswerMismatch(String),
+
+    #[error("Submission refused: {0}")]
+    SubmitRefused(String),
+
     #[error("Internal error: {0}")]
     Other(String),
 }
 
+impl AppError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::DataOpen(_) => ErrorKind::DataOpen,
+            Self::DataParse(_) => ErrorKind::DataParse,
+            Self::LogicalError(_) => ErrorKind::Logical,
+            Self::NotImplemented(_) => ErrorKind::NotImplemented,
+            Self::Panic { .. } => ErrorKind::Panic,
+            Self::Timeout(_) => ErrorKind::Timeout,
+            Self::InvalidParam(_) => ErrorKind::InvalidParam,
+            Self::AnswerMismatch(_) => ErrorKind::AnswerMismatch,
+            Self::SubmitRefused(_) => ErrorKind::SubmitRefused,
+            Self::Other(_) => ErrorKind::Internal,
+        }
+    }
+}
+
+/// Category of a failure, each one exits the process with its own code
+///
+/// | Code | Kind              | Cause                                         |
+/// |------|-------------------|-----------------------------------------------|
+/// | 0    |                   | Success                                       |
+/// | 1    | `internal`        | Any error that is not an [`AppError`]         |
+/// | 2    |                   | Invalid command line, reported by clap        |
+/// | 3    | `data_open`       | Input or answers file cannot be read          |
+/// | 4    | `data_parse`      | Malformed input                               |
+/// | 5    | `logical`         | Solution rejected its input                   |
+/// | 6    | `not_implemented` | No solution for the day                       |
+/// | 7    | `invalid_param`   | Unknown or mistyped `--param`                 |
+/// | 8    | `answer_mismatch` | Answer differs from the recorded one          |
+/// | 9    | `timeout`         | No answer within `--timeout`                  |
+/// | 10   | `panic`           | Solution panicked                             |
+/// | 11   | `submit_refused`  | Cooldown, solved level or known wrong answer  |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Internal = 1,
+    DataOpen = 3,
+    DataParse = 4,
+    Logical = 5,
+    NotImplemented = 6,
+    InvalidParam = 7,
+    AnswerMismatch = 8,
+    Timeout = 9,
+    Panic = 10,
+    SubmitRefused = 11,
+}
+
+impl ErrorKind {
+    pub const ALL: [ErrorKind; 10] = [
+        ErrorKind::Internal,
+        ErrorKind::DataOpen,
+        ErrorKind::DataParse,
+        ErrorKind::Logical,
+        ErrorKind::NotImplemented,
+        ErrorKind::InvalidParam,
+        ErrorKind::AnswerMismatch,
+        ErrorKind::Timeout,
+        ErrorKind::Panic,
+        ErrorKind::SubmitRefused,
+    ];
+
+    pub fn exit_code(self) -> u8 {
+        self as u8
+    }
+
+    /// Name of the kind in json and csv output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::Internal => "internal",
+            ErrorKind::DataOpen => "data_open",
+            ErrorKind::DataParse => "data_parse",
+            ErrorKind::Logical => "logical",
+            ErrorKind::NotImplemented => "not_implemented",
+            ErrorKind::InvalidParam => "invalid_param",
+            ErrorKind::AnswerMismatch => "answer_mismatch",
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::Panic => "panic",
+            ErrorKind::SubmitRefused => "submit_refused",
+        }
+    }
+}
+
 impl From<Box<dyn Error + Send + Sync>> for AppError {
     fn from(value: Box<dyn Error + Send + Sync>) -> Self {
         Self::Other(value.to_string())
     }
 }
+
+/// Malformed input, optionally pointing at the offending text
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub day: Option<u8>,
+    /// 1-based line number
+    pub line: Option<usize>,
+    /// 0-based char range of the offending text in `text`
+    pub columns: Option<Range<usize>>,
+    /// Whole line the error points at
+    pub text: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Points at `columns` of the 1-based `line`
+    pub fn at(mut self, line: usize, text: impl Into<String>, columns: Range<usize>) -> Self {
+        self.line = Some(line);
+        self.text = Some(text.into());
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Points at the whole 1-based `line`
+    pub fn on_line(self, line: usize, text: impl Into<String>) -> Self {
+        let text = text.into();
+        let columns = 0..text.chars().count();
+        self.at(line, text, columns)
+    }
+
+    /// Offending line with a caret under the columns, `None` without a line
+    pub fn snippet(&self) -> Option<String> {
+        let (line, text) = (self.line?, self.text.as_ref()?);
+        let columns = self.columns.clone().unwrap_or(0..0);
+        let gutter = " ".repeat(line.to_string().len());
+        Some(format!(
+            "{gutter} |\n{line} | {text}\n{gutter} | {}{} {}",
+            " ".repeat(columns.start),
+            "^".repeat(columns.len().max(1)),
+            self.message
+        ))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut location = vec![];
+        if let Some(day) = self.day {
+            location.push(format!("day {day}"));
+        }
+        if let Some(line) = self.line {
+            location.push(format!("line {line}"));
+        }
+        match &self.columns {
+            Some(columns) if columns.len() > 1 => {
+                location.push(format!("columns {}-{}", columns.start + 1, columns.end))
+            }
+            Some(columns) => location.push(format!("column {}", columns.start + 1)),
+            None => {}
+        }
+        if location.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", location.join(", "), self.message)
+        }
+    }
+}
+
+impl From<String> for ParseError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<&str> for ParseError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_error_points_at_columns() {
+        let err = ParseError::new("Unknown character `x`").at(12, "..#.x..", 4..5);
+        assert_eq!(err.to_string(), "line 12, column 5: Unknown character `x`");
+        assert_eq!(
+            err.snippet().unwrap(),
+            "   |\n12 | ..#.x..\n   |     ^ Unknown character `x`"
+        );
+        assert_eq!(ParseError::new("empty").snippet(), None);
+    }
+
+    #[test]
+    fn parse_context_sets_day() {
+        let report: Report = AppError::DataParse(ParseError::new("bad").on_line(3, "1 x")).into();
+        assert_eq!(
+            report.with_parse_context(7).to_string(),
+            "Data File Parsing Error: day 7, line 3, columns 1-3: bad"
+        );
+    }
+
+    #[test]
+    fn kind_decides_exit_code() {
+        let report: Report = AppError::Timeout("slow".into()).into();
+        assert_eq!(report.kind(), ErrorKind::Timeout);
+        assert_eq!(report.kind().exit_code(), 9);
+        let report: Report = std::io::Error::other("io").into();
+        assert_eq!(report.kind().exit_code(), 1);
+        assert_eq!(
+            serde_json::to_string(&AppError::NotImplemented(3).kind()).unwrap(),
+            r#""not_implemented""#
+        );
+        for kind in ErrorKind::ALL {
+            assert_eq!(
+                serde_json::to_string(&kind).unwrap(),
+                format!("\"{}\"", kind.as_str())
+            );
+        }
+    }
+}
@@ -1,18 +1,30 @@
 use crate::constants::PACKAGE_NAME;
-use std::io::IsTerminal;
-use tracing::Subscriber;
+use color_eyre::eyre::eyre;
+use std::{
+    fs::File,
+    io::{BufWriter, IsTerminal},
+    path::PathBuf,
+};
+use tracing::{level_filters::LevelFilter, Subscriber};
+use tracing_chrome::ChromeLayerBuilder;
+use tracing_flame::FlameLayer;
 use tracing_subscriber::{
     filter::Directive,
+    fmt::MakeWriter,
     layer::{Layer, SubscriberExt},
     registry::LookupSpan,
     util::SubscriberInitExt,
-    EnvFilter,
+    EnvFilter, Registry,
 };
 
-use super::logger::Logger;
+use super::{
+    log_file::RotatingFile,
+    logger::Logger,
+    timings::{self, Timings},
+};
 
-static OUT: fn() -> std::io::Stdout = std::io::stdout;
-// static OUT: fn() -> std::io::Stderr = std::io::stderr;
+// Logs stay on stderr so stdout only carries results
+static OUT: fn() -> std::io::Stderr = std::io::stderr;
 
 #[derive(clap::Args, Debug, Default, Clone)]
 pub struct Instrumentation {
@@ -43,9 +55,55 @@ pub struct Instrumentation {
         global = true
     )]
     pub log_directives: Vec<Directive>,
+
+    /// Also write logs to this file
+    #[clap(long, global = true)]
+    pub log_file: Option<PathBuf>,
+
+    /// Log level of the log file, independent of `-v` [default: same as terminal]
+    #[clap(long, global = true)]
+    pub log_file_level: Option<LevelFilter>,
+
+    /// Which text tracing-setup to use for the log file [default: same as --logger, or compact]
+    #[clap(long, value_parser = Logger::text_parser(), global = true)]
+    pub log_file_logger: Option<Logger>,
+
+    /// Rotate the log file once it grows past this many bytes
+    #[clap(long, global = true)]
+    pub log_file_max_size: Option<u64>,
+
+    /// Number of rotated log files to keep
+    #[clap(long, default_value_t = 3, global = true)]
+    pub log_file_keep: usize,
+
+    /// Output of the `chrome-trace` and `folded` loggers [default: trace.json or trace.folded]
+    #[clap(long, global = true)]
+    pub trace_out: Option<PathBuf>,
+
+    /// Print busy/idle time per span name when the run ends
+    #[clap(long, global = true)]
+    pub timings: bool,
+}
+
+/// Flushes the trace file of the `chrome-trace` and `folded` loggers when dropped
+#[derive(Default)]
+pub struct InstrumentationGuard {
+    _chrome: Option<tracing_chrome::FlushGuard>,
+    _flame: Option<tracing_flame::FlushGuard<BufWriter<File>>>,
+    _timings: Option<Timings>,
 }
 
 impl Instrumentation {
+    /// Logger of the log file, `--logger` unless it only writes to `--trace-out`
+    pub fn file_logger(&self) -> Logger {
+        self.log_file_logger
+            .unwrap_or(if Logger::TEXT.contains(&self.logger) {
+                self.logger
+            } else {
+                Logger::Compact
+            })
+    }
+
     pub fn log_level(&self) -> String {
         match self.verbose {
             0 => "info",
@@ -55,28 +113,66 @@ impl Instrumentation {
         .to_string()
     }
 
-    pub fn setup(&self, packages: &[&str]) -> color_eyre::Result<()> {
-        let filter_layer = self.filter_layer(packages)?;
+    pub fn setup(&self, packages: &[&str]) -> color_eyre::Result<InstrumentationGuard> {
+        let mut guard = InstrumentationGuard::default();
+        let main_layer = match self.logger {
+            Logger::ChromeTrace => {
+                let path = self.trace_out.clone().unwrap_or("trace.json".into());
+                let (layer, flush) = ChromeLayerBuilder::new()
+                    .writer(File::create(path)?)
+                    .include_args(true)
+                    .build();
+                guard._chrome = Some(flush);
+                layer.boxed()
+            }
+            Logger::Folded => {
+                let path = self.trace_out.clone().unwrap_or("trace.folded".into());
+                let (layer, flush) = FlameLayer::with_file(path)?;
+                guard._flame = Some(flush);
+                layer.boxed()
+            }
+            logger => self.fmt_layer(logger, OUT, OUT().is_terminal())?,
+        };
 
-        let registry = tracing_subscriber::registry()
-            .with(filter_layer)
-            .with(tracing_error::ErrorLayer::default());
+        // Every layer has its own filter, so the terminal and the log file can differ in verbosity
+        let mut layers: Vec<Box<dyn Layer<Registry> + Send + Sync>> = vec![
+            tracing_error::ErrorLayer::default()
+                .with_filter(self.filter_layer(packages)?)
+                .boxed(),
+            main_layer.with_filter(self.filter_layer(packages)?).boxed(),
+        ];
 
-        // `try_init` called inside `match` since `with` changes the type
-        match self.logger {
-            Logger::Compact => registry.with(self.fmt_layer_compact()).try_init()?,
-            Logger::Full => registry.with(self.fmt_layer_full()).try_init()?,
-            Logger::Pretty => registry.with(self.fmt_layer_pretty()).try_init()?,
-            Logger::Json => registry.with(self.fmt_layer_json()).try_init()?,
+        if let Some(path) = &self.log_file {
+            let file = RotatingFile::open(path, self.log_file_max_size, self.log_file_keep)?;
+            let log_level = self
+                .log_file_level
+                .map_or_else(|| self.log_level(), |level| level.to_string());
+            layers.push(
+                self.fmt_layer(self.file_logger(), file, false)?
+                    .with_filter(self.filter(packages, &log_level)?)
+                    .boxed(),
+            );
         }
 
-        Ok(())
+        if self.timings {
+            let (layer, timings) = timings::timings();
+            layers.push(layer.with_filter(self.filter_layer(packages)?).boxed());
+            guard._timings = Some(timings);
+        }
+
+        tracing_subscriber::registry().with(layers).try_init()?;
+
+        Ok(guard)
     }
 
+    /// Terminal filter, from `-v` and directives
     pub fn filter_layer(&self, packages: &[&str]) -> color_eyre::Result<EnvFilter> {
+        self.filter(packages, &self.log_level())
+    }
+
+    pub fn filter(&self, packages: &[&str], log_level: &str) -> color_eyre::Result<EnvFilter> {
         let mut filter_layer = {
             if self.log_directives.is_empty() {
-                let log_level = self.log_level();
                 let default = packages
                     .iter()
                     .map(|p| p.replace('-', "_"))
@@ -99,42 +195,69 @@ impl Instrumentation {
         Ok(filter_layer)
     }
 
-    pub fn fmt_layer_full<S>(&self) -> impl Layer<S>
+    pub fn fmt_layer<S, W>(
+        &self,
+        logger: Logger,
+        writer: W,
+        ansi: bool,
+    ) -> color_eyre::Result<Box<dyn Layer<S> + Send + Sync>>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+        W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+    {
+        Ok(match logger {
+            Logger::Compact => self.fmt_layer_compact(writer, ansi).boxed(),
+            Logger::Full => self.fmt_layer_full(writer, ansi).boxed(),
+            Logger::Pretty => self.fmt_layer_pretty(writer, ansi).boxed(),
+            Logger::Json => self.fmt_layer_json(writer, ansi).boxed(),
+            Logger::ChromeTrace | Logger::Folded => {
+                return Err(eyre!(
+                    "`{logger}` logger writes to --trace-out, not a text stream"
+                ))
+            }
+        })
+    }
+
+    pub fn fmt_layer_full<S, W>(&self, writer: W, ansi: bool) -> impl Layer<S>
     where
         S: Subscriber + for<'span> LookupSpan<'span>,
+        W: for<'writer> MakeWriter<'writer> + 'static,
     {
         tracing_subscriber::fmt::Layer::new()
-            .with_ansi(OUT().is_terminal())
-            .with_writer(OUT)
+            .with_ansi(ansi)
+            .with_writer(writer)
     }
 
-    pub fn fmt_layer_pretty<S>(&self) -> impl Layer<S>
+    pub fn fmt_layer_pretty<S, W>(&self, writer: W, ansi: bool) -> impl Layer<S>
     where
         S: Subscriber + for<'span> LookupSpan<'span>,
+        W: for<'writer> MakeWriter<'writer> + 'static,
     {
         tracing_subscriber::fmt::Layer::new()
-            .with_ansi(OUT().is_terminal())
-            .with_writer(OUT)
+            .with_ansi(ansi)
+            .with_writer(writer)
             .pretty()
     }
 
-    pub fn fmt_layer_json<S>(&self) -> impl Layer<S>
+    pub fn fmt_layer_json<S, W>(&self, writer: W, ansi: bool) -> impl Layer<S>
     where
         S: Subscriber + for<'span> LookupSpan<'span>,
+        W: for<'writer> MakeWriter<'writer> + 'static,
     {
         tracing_subscriber::fmt::Layer::new()
-            .with_ansi(OUT().is_terminal())
-            .with_writer(OUT)
+            .with_ansi(ansi)
+            .with_writer(writer)
             .json()
     }
 
-    pub fn fmt_layer_compact<S>(&self) -> impl Layer<S>
+    pub fn fmt_layer_compact<S, W>(&self, writer: W, ansi: bool) -> impl Layer<S>
     where
         S: Subscriber + for<'span> LookupSpan<'span>,
+        W: for<'writer> MakeWriter<'writer> + 'static,
     {
         tracing_subscriber::fmt::Layer::new()
-            .with_ansi(OUT().is_terminal())
-            .with_writer(OUT)
+            .with_ansi(ansi)
+            .with_writer(writer)
             .compact()
             .without_time()
             .with_target(false)
@@ -150,8 +273,7 @@ impl Instrumentation {
 fn init() {
     Instrumentation {
         verbose: 2,
-        logger: Default::default(),
-        log_directives: vec![],
+        ..Default::default()
     }
     .setup(&[])
     .unwrap();
@@ -0,0 +1,123 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Log file that is rotated to `{path}.1`, `{path}.2`, ... once it grows past `max_size`
+pub struct RotatingFile {
+    path: PathBuf,
+    max_size: Option<u64>,
+    keep: usize,
+    state: Mutex<FileState>,
+}
+
+struct FileState {
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    /// Opens `path` for appending, `keep` is the number of rotated files to retain
+    pub fn open(path: &Path, max_size: Option<u64>, keep: usize) -> io::Result<Self> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            max_size,
+            keep,
+            state: Mutex::new(FileState { file, size }),
+        })
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{n}"));
+        name.into()
+    }
+
+    fn rotate(&self, state: &mut FileState) -> io::Result<()> {
+        state.file.flush()?;
+        if self.keep > 0 {
+            for n in (1..self.keep).rev() {
+                let from = self.rotated(n);
+                if from.exists() {
+                    fs::rename(from, self.rotated(n + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        state.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        state.size = 0;
+        Ok(())
+    }
+
+    fn write_record(&self, buf: &[u8]) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        // Records are never split, a record larger than `max_size` gets a file of its own
+        if self
+            .max_size
+            .is_some_and(|max| state.size > 0 && state.size + buf.len() as u64 > max)
+        {
+            self.rotate(&mut state)?;
+        }
+        state.file.write_all(buf)?;
+        state.size += buf.len() as u64;
+        Ok(())
+    }
+}
+
+/// Writer handed out to the fmt layer, one per log record
+pub struct RotatingWriter<'a>(&'a RotatingFile);
+
+impl Write for RotatingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write_record(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut state = self.0.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.file.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFile {
+    type Writer = RotatingWriter<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RotatingWriter(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rotates_by_size() {
+        let dir = std::env::temp_dir().join(format!("advent_2024_log_{}", std::process::id()));
+        let path = dir.join("run.log");
+        let log = RotatingFile::open(&path, Some(10), 2).unwrap();
+        for record in ["first\n", "second\n", "third\n", "fourth\n"] {
+            log.make_writer().write_all(record.as_bytes()).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fourth\n");
+        assert_eq!(fs::read_to_string(log.rotated(1)).unwrap(), "third\n");
+        assert_eq!(fs::read_to_string(log.rotated(2)).unwrap(), "second\n");
+        assert!(!log.rotated(3).exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
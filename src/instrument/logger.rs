@@ -1,10 +1,30 @@
-#[derive(Copy, Clone, Default, Debug, clap::ValueEnum)]
+use clap::{
+    builder::{PossibleValuesParser, TypedValueParser},
+    ValueEnum,
+};
+
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, clap::ValueEnum)]
 pub enum Logger {
     #[default]
     Compact,
     Full,
     Pretty,
     Json,
+    /// Chrome trace-event JSON of span enter/exit per thread, written to `--trace-out`
+    ChromeTrace,
+    /// Collapsed stacks of span busy time for flamegraph tools, written to `--trace-out`
+    Folded,
+}
+
+impl Logger {
+    /// Loggers writing text lines, the only ones a log file can use
+    pub const TEXT: [Logger; 4] = [Logger::Compact, Logger::Full, Logger::Pretty, Logger::Json];
+
+    /// Parser accepting only the [`Logger::TEXT`] loggers
+    pub fn text_parser() -> impl TypedValueParser<Value = Logger> {
+        PossibleValuesParser::new(Self::TEXT.map(|logger| logger.to_possible_value().unwrap()))
+            .map(|logger| <Logger as ValueEnum>::from_str(&logger, false).unwrap())
+    }
 }
 
 impl std::fmt::Display for Logger {
@@ -14,6 +34,8 @@ impl std::fmt::Display for Logger {
             Logger::Full => "full",
             Logger::Pretty => "pretty",
             Logger::Json => "json",
+            Logger::ChromeTrace => "chrome-trace",
+            Logger::Folded => "folded",
         };
         write!(f, "{}", logger)
     }
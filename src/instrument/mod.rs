@@ -1,2 +1,4 @@
 pub mod instrumentation;
+pub mod log_file;
 pub mod logger;
+pub mod timings;
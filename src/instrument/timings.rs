@@ -0,0 +1,220 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{span, Subscriber};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+/// Time of a single open span, kept in its extensions
+struct SpanTiming {
+    busy: Duration,
+    idle: Duration,
+    /// Busy time of closed child spans
+    children: Duration,
+    last: Instant,
+}
+
+/// Totals of every closed span with the same name
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SpanStats {
+    pub count: u64,
+    pub busy: Duration,
+    /// Busy time outside of child spans, does not double count recursion
+    pub busy_self: Duration,
+    pub idle: Duration,
+    /// Longest busy time of a single span
+    pub max: Duration,
+}
+
+/// Span `(target, name)` to its totals
+type StatsMap = HashMap<(&'static str, &'static str), SpanStats>;
+
+/// Layer summing busy and idle time per span name
+pub struct TimingsLayer {
+    stats: Arc<Mutex<StatsMap>>,
+}
+
+/// Summary of [`TimingsLayer`], printed to stderr when dropped
+pub struct Timings {
+    stats: Arc<Mutex<StatsMap>>,
+    start: Instant,
+}
+
+pub fn timings() -> (TimingsLayer, Timings) {
+    let stats = Arc::new(Mutex::new(HashMap::new()));
+    (
+        TimingsLayer {
+            stats: stats.clone(),
+        },
+        Timings {
+            stats,
+            start: Instant::now(),
+        },
+    )
+}
+
+impl<S> Layer<S> for TimingsLayer
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanTiming {
+                busy: Duration::ZERO,
+                idle: Duration::ZERO,
+                children: Duration::ZERO,
+                last: Instant::now(),
+            });
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let now = Instant::now();
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+                timing.idle += now.saturating_duration_since(timing.last);
+                timing.last = now;
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let now = Instant::now();
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+                timing.busy += now.saturating_duration_since(timing.last);
+                timing.last = now;
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let now = Instant::now();
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        let Some(timing) = extensions.get::<SpanTiming>() else {
+            return;
+        };
+        let idle = timing.idle + now.saturating_duration_since(timing.last);
+        let metadata = span.metadata();
+        {
+            let mut stats = self.stats.lock().unwrap_or_else(|err| err.into_inner());
+            let entry = stats
+                .entry((metadata.target(), metadata.name()))
+                .or_default();
+            entry.count += 1;
+            entry.busy += timing.busy;
+            entry.busy_self += timing.busy.saturating_sub(timing.children);
+            entry.idle += idle;
+            entry.max = entry.max.max(timing.busy);
+        }
+        if let Some(parent) = span.parent() {
+            if let Some(parent_timing) = parent.extensions_mut().get_mut::<SpanTiming>() {
+                parent_timing.children += timing.busy;
+            }
+        }
+    }
+}
+
+impl Timings {
+    /// Span names with their totals, longest self time first
+    pub fn rows(&self) -> Vec<(String, SpanStats)> {
+        let stats = self.stats.lock().unwrap_or_else(|err| err.into_inner());
+        let mut rows: Vec<(String, SpanStats)> = stats
+            .iter()
+            .map(|((target, name), stats)| {
+                // Last module of the target is enough to tell the days apart
+                let module = target.rsplit("::").next().unwrap_or(target);
+                (format!("{module}::{name}"), *stats)
+            })
+            .collect();
+        rows.sort_by(|(a_name, a), (b_name, b)| {
+            b.busy_self.cmp(&a.busy_self).then(a_name.cmp(b_name))
+        });
+        rows
+    }
+
+    pub fn write_table(&self, mut out: impl Write) -> io::Result<()> {
+        let rows = self.rows();
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let total = self.start.elapsed();
+        let width = rows
+            .iter()
+            .map(|(name, _)| name.len())
+            .chain(["Span".len()])
+            .max()
+            .unwrap_or_default();
+        let fmt = |d: Duration| format!("{d:.2?}");
+        writeln!(
+            out,
+            "{:<width$} | {:>8} | {:>10} | {:>10} | {:>6} | {:>10} | {:>10}",
+            "Span", "Count", "Busy", "Self", "Self %", "Idle", "Max"
+        )?;
+        writeln!(
+            out,
+            "{}-+-{}",
+            "-".repeat(width),
+            [8, 10, 10, 6, 10, 10].map(|w| "-".repeat(w)).join("-+-")
+        )?;
+        for (name, stats) in rows {
+            let share =
+                stats.busy_self.as_secs_f64() / total.as_secs_f64().max(f64::EPSILON) * 100.0;
+            writeln!(
+                out,
+                "{name:<width$} | {:>8} | {:>10} | {:>10} | {:>5.1}% | {:>10} | {:>10}",
+                stats.count,
+                fmt(stats.busy),
+                fmt(stats.busy_self),
+                share,
+                fmt(stats.idle),
+                fmt(stats.max)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Timings {
+    fn drop(&mut self) {
+        let _ = self.write_table(io::stderr().lock());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tracing::info_span;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[test]
+    fn sums_spans_by_name() {
+        let (layer, timings) = timings();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let outer = info_span!("outer");
+            let _outer = outer.enter();
+            for _ in 0..3 {
+                let _inner = info_span!("inner").entered();
+                std::thread::sleep(Duration::from_millis(2));
+            }
+        });
+
+        let rows = timings.rows();
+        let names: Vec<&str> = rows.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["test::inner", "test::outer"]);
+        let (inner, outer) = (rows[0].1, rows[1].1);
+        assert_eq!((outer.count, inner.count), (1, 3));
+        assert!(inner.busy >= Duration::from_millis(6));
+        assert!(inner.max >= Duration::from_millis(2) && inner.max <= inner.busy);
+        assert!(outer.busy >= inner.busy);
+        assert_eq!(outer.busy_self, outer.busy - inner.busy);
+    }
+}
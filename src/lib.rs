@@ -1,6 +1,9 @@
+pub mod aoc;
 pub mod cli;
 pub mod constants;
 pub mod error;
 pub mod instrument;
+pub mod runner;
+pub mod scaffold;
 pub mod solutions;
 pub mod utils;
@@ -0,0 +1,213 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::{
+    error::{AppError, Result},
+    solutions::{self, Answer, Params, Star},
+};
+
+use super::run;
+
+/// Answers file name inside the inputs directory
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Answer known to be accepted for a day/star/input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedAnswer {
+    pub day: u8,
+    pub star: u8,
+    pub input: PathBuf,
+    pub answer: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AnswerBook {
+    #[serde(default, rename = "answer")]
+    pub answers: Vec<RecordedAnswer>,
+}
+
+/// Recorded answer next to the one produced by the current code
+#[derive(Debug)]
+pub struct Verification {
+    pub recorded: RecordedAnswer,
+    pub actual: Result<Answer, String>,
+}
+
+impl Verification {
+    pub fn is_match(&self) -> bool {
+        self.actual
+            .as_ref()
+            .is_ok_and(|answer| answer.to_string() == self.recorded.answer)
+    }
+
+    /// Writes `ok` line for a match, or a diff of expected and actual answers
+    pub fn write_diff(&self, mut out: impl Write) -> io::Result<()> {
+        let RecordedAnswer {
+            day, star, input, ..
+        } = &self.recorded;
+        if self.is_match() {
+            return writeln!(out, "ok   day {day} star {star} ({})", input.display());
+        }
+        writeln!(out, "FAIL day {day} star {star} ({})", input.display())?;
+        writeln!(out, "- {}", self.recorded.answer)?;
+        match &self.actual {
+            Ok(answer) => writeln!(out, "+ {answer}"),
+            Err(err) => writeln!(out, "+ error: {err}"),
+        }
+    }
+}
+
+/// [`AppError::AnswerMismatch`] when any recorded answer changed
+pub fn check(verifications: &[Verification]) -> Result<(), AppError> {
+    let failed = verifications.iter().filter(|v| !v.is_match()).count();
+    if failed > 0 {
+        return Err(AppError::AnswerMismatch(format!(
+            "{failed} of {} recorded answers changed",
+            verifications.len()
+        )));
+    }
+    Ok(())
+}
+
+impl AnswerBook {
+    /// Loads answers file, missing file is an empty book
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(AppError::DataOpen(format!("{}: {err}", path.display())).into()),
+        };
+        toml::from_str(&content)
+            .map_err(|err| AppError::DataParse(format!("{}: {err}", path.display()).into()).into())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string(self).map_err(|err| AppError::Other(err.to_string()))?;
+        fs::write(path, content)
+            .map_err(|err| AppError::DataOpen(format!("{}: {err}", path.display())).into())
+    }
+
+    /// Adds or replaces the answer for given day/star/input
+    pub fn record(&mut self, day: u8, star: Star, input: &Path, answer: &Answer) {
+        let recorded = RecordedAnswer {
+            day,
+            star: star as u8,
+            input: input.to_path_buf(),
+            answer: answer.to_string(),
+        };
+        match self
+            .answers
+            .iter_mut()
+            .find(|r| (r.day, r.star, &r.input) == (day, star as u8, &recorded.input))
+        {
+            Some(existing) => *existing = recorded,
+            None => self.answers.push(recorded),
+        }
+        self.answers
+            .sort_by(|a, b| (a.day, a.star, &a.input).cmp(&(b.day, b.star, &b.input)));
+    }
+
+    /// Re-runs every recorded answer with default parameters, parsing each input once
+    #[instrument(skip_all)]
+    pub fn verify(&self, timeout: Option<Duration>) -> Vec<Verification> {
+        self.answers
+            .iter()
+            .into_group_map_by(|r| (r.day, r.input.clone()))
+            .into_iter()
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .flat_map(|((day, input), recorded)| {
+                debug!(day = day, input = ?input, count = recorded.len(), "Verifying");
+                let stars = recorded
+                    .iter()
+                    .map(|r| Star::try_from(r.star))
+                    .collect::<Result<Vec<Star>, AppError>>();
+                let actual: Vec<Result<Answer, String>> = match (solutions::find(day), stars) {
+                    (Some(puzzle), Ok(stars)) => {
+                        let params = Params::defaults(puzzle.params());
+                        run(puzzle, &stars, &input, &params, timeout)
+                            .into_iter()
+                            .map(|report| report.answer.map_err(|err| err.to_string()))
+                            .collect()
+                    }
+                    (None, _) => {
+                        vec![Err(AppError::NotImplemented(day).to_string()); recorded.len()]
+                    }
+                    (_, Err(err)) => vec![Err(err.to_string()); recorded.len()],
+                };
+                recorded
+                    .into_iter()
+                    .zip(actual)
+                    .map(|(recorded, actual)| Verification {
+                        recorded: recorded.clone(),
+                        actual,
+                    })
+                    .collect_vec()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "examples/day1/1.txt";
+
+    #[test]
+    fn record_save_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("advent_2024_answers_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(ANSWERS_FILE);
+
+        let mut book = AnswerBook::load(&path).unwrap();
+        assert!(book.answers.is_empty());
+        book.record(1, Star::Two, Path::new(INPUT), &"30".into());
+        book.record(1, Star::One, Path::new(INPUT), &"11".into());
+        book.record(1, Star::Two, Path::new(INPUT), &"31".into());
+        book.save(&path).unwrap();
+
+        let loaded = AnswerBook::load(&path).unwrap();
+        assert_eq!(loaded.answers, book.answers);
+        assert_eq!(
+            loaded
+                .answers
+                .iter()
+                .map(|r| (r.star, r.answer.as_str()))
+                .collect_vec(),
+            [(1, "11"), (2, "31")]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn verify_reports_mismatch() {
+        let mut book = AnswerBook::default();
+        book.record(1, Star::One, Path::new(INPUT), &"11".into());
+        book.record(1, Star::Two, Path::new(INPUT), &"30".into());
+
+        let verifications = book.verify(None);
+        let mut out = vec![];
+        for verification in verifications.iter() {
+            verification.write_diff(&mut out).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("ok   day 1 star 1 ({INPUT})\nFAIL day 1 star 2 ({INPUT})\n- 30\n+ 31\n")
+        );
+        assert_eq!(
+            check(&verifications),
+            Err(AppError::AnswerMismatch(
+                "1 of 2 recorded answers changed".into()
+            ))
+        );
+        assert_eq!(check(&verifications[..1]), Ok(()));
+    }
+}
@@ -0,0 +1,332 @@
+use std::{
+    io::{self, Read, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+    error::{AppError, Result},
+    solutions::{Params, Puzzle, Star},
+};
+
+use super::{
+    guard::catch_panic,
+    input,
+    memory::{self, format_bytes, MemoryStats},
+    open_input,
+};
+
+#[derive(Copy, Clone, Default, Debug, clap::ValueEnum)]
+pub enum BenchFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+/// Timing statistics over the measured iterations, in nanoseconds
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+        let len = nanos.len().max(1) as f64;
+        let median = match nanos.len() {
+            0 => 0.0,
+            n if n % 2 == 0 => (nanos[n / 2 - 1] + nanos[n / 2]) / 2.0,
+            n => nanos[n / 2],
+        };
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / len;
+        Self {
+            min_ns: nanos.first().copied().unwrap_or_default() as u64,
+            median_ns: median as u64,
+            mean_ns: mean as u64,
+            stddev_ns: variance.sqrt() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct StarBench {
+    pub star: u8,
+    pub solve: Stats,
+    /// Heap usage of the last run, only with the `count-alloc` feature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryStats>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub day: u8,
+    /// Size of the rayon pool the benchmark ran in
+    pub threads: usize,
+    pub iterations: usize,
+    pub warmup: usize,
+    pub parse: Stats,
+    /// Heap usage of the last parse, only with the `count-alloc` feature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_memory: Option<MemoryStats>,
+    pub stars: Vec<StarBench>,
+}
+
+/// Benchmark of the same day on a single thread and on the whole rayon pool
+#[derive(Debug, Serialize)]
+pub struct Comparison {
+    pub single: BenchReport,
+    pub multi: BenchReport,
+}
+
+fn threads_label(threads: usize) -> String {
+    match threads {
+        1 => "1 thread".to_string(),
+        n => format!("{n} threads"),
+    }
+}
+
+/// Reads the input into memory, so that disk access is not part of the parse time
+fn read_input(path: &Path) -> Result<Vec<u8>> {
+    let mut data = vec![];
+    open_input(path)?
+        .read_to_end(&mut data)
+        .map_err(|err| AppError::DataOpen(format!("{}: {err}", path.display())))?;
+    Ok(data)
+}
+
+/// Runs parse and every star `warmup + iterations` times, only the last `iterations` are measured
+pub fn bench(
+    puzzle: &dyn Puzzle,
+    stars: &[Star],
+    path: &Path,
+    params: &Params,
+    iterations: usize,
+    warmup: usize,
+) -> Result<BenchReport> {
+    let data = read_input(path)?;
+    bench_data(puzzle, stars, &data, params, iterations, warmup)
+}
+
+/// Runs [`bench`] in a single threaded pool and then in the global pool
+pub fn compare(
+    puzzle: &dyn Puzzle,
+    stars: &[Star],
+    path: &Path,
+    params: &Params,
+    iterations: usize,
+    warmup: usize,
+) -> Result<Comparison> {
+    let data = read_input(path)?;
+    let single_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .map_err(|err| AppError::Other(err.to_string()))?;
+    let single =
+        single_pool.install(|| bench_data(puzzle, stars, &data, params, iterations, warmup))?;
+    let multi = bench_data(puzzle, stars, &data, params, iterations, warmup)?;
+    Ok(Comparison { single, multi })
+}
+
+fn bench_data(
+    puzzle: &dyn Puzzle,
+    stars: &[Star],
+    data: &[u8],
+    params: &Params,
+    iterations: usize,
+    warmup: usize,
+) -> Result<BenchReport> {
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut solve_samples = vec![Vec::with_capacity(iterations); stars.len()];
+    let mut parse_memory = None;
+    let mut solve_memory = vec![None; stars.len()];
+    for i in 0..warmup + iterations {
+        let start = Instant::now();
+        let (parsed, memory) = memory::measure(|| catch_panic(|| puzzle.parse(input(data))));
+        let parse_time = start.elapsed();
+        let parsed = parsed.map_err(|err| err.with_parse_context(puzzle.day()))?;
+        parse_memory = memory;
+
+        let mut solve_times = Vec::with_capacity(stars.len());
+        for (&star, last_memory) in stars.iter().zip(solve_memory.iter_mut()) {
+            let start = Instant::now();
+            let (answer, memory) =
+                memory::measure(|| catch_panic(|| puzzle.solve(star, &parsed, params)));
+            solve_times.push(start.elapsed());
+            answer?;
+            *last_memory = memory;
+        }
+
+        if i >= warmup {
+            parse_samples.push(parse_time);
+            for (samples, time) in solve_samples.iter_mut().zip(solve_times) {
+                samples.push(time);
+            }
+        }
+    }
+
+    Ok(BenchReport {
+        day: puzzle.day(),
+        threads: rayon::current_num_threads(),
+        iterations,
+        warmup,
+        parse: Stats::from_samples(&parse_samples),
+        parse_memory,
+        stars: stars
+            .iter()
+            .zip(solve_samples)
+            .zip(solve_memory)
+            .map(|((&star, samples), memory)| StarBench {
+                star: star as u8,
+                solve: Stats::from_samples(&samples),
+                memory,
+            })
+            .collect(),
+    })
+}
+
+impl BenchReport {
+    pub fn write(&self, mut out: impl Write, format: BenchFormat) -> Result<()> {
+        match format {
+            BenchFormat::Table => self.write_table(&mut out)?,
+            BenchFormat::Json => {
+                serde_json::to_writer_pretty(&mut out, self)
+                    .map_err(|err| AppError::Other(err.to_string()))?;
+                writeln!(out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_table(&self, mut out: impl Write) -> io::Result<()> {
+        let fmt = |ns: u64| format!("{:.2?}", Duration::from_nanos(ns));
+        writeln!(
+            out,
+            "Day {}: {} iterations after {} warmup on {}",
+            self.day,
+            self.iterations,
+            self.warmup,
+            threads_label(self.threads)
+        )?;
+        let with_memory = self.parse_memory.is_some();
+        write!(
+            out,
+            "{:<7} | {:>10} | {:>10} | {:>10} | {:>10}",
+            "Stage", "Min", "Median", "Mean", "Stddev"
+        )?;
+        if with_memory {
+            write!(
+                out,
+                " | {:>10} | {:>10} | {:>8}",
+                "Peak", "Allocated", "Allocs"
+            )?;
+        }
+        writeln!(out)?;
+        write!(
+            out,
+            "{}-+-{}",
+            "-".repeat(7),
+            vec!["-".repeat(10); 4].join("-+-")
+        )?;
+        if with_memory {
+            write!(
+                out,
+                "-+-{}-+-{}",
+                vec!["-".repeat(10); 2].join("-+-"),
+                "-".repeat(8)
+            )?;
+        }
+        writeln!(out)?;
+        let rows = std::iter::once(("parse".to_string(), &self.parse, self.parse_memory)).chain(
+            self.stars
+                .iter()
+                .map(|s| (format!("star {}", s.star), &s.solve, s.memory)),
+        );
+        for (stage, stats, memory) in rows {
+            write!(
+                out,
+                "{stage:<7} | {:>10} | {:>10} | {:>10} | {:>10}",
+                fmt(stats.min_ns),
+                fmt(stats.median_ns),
+                fmt(stats.mean_ns),
+                fmt(stats.stddev_ns)
+            )?;
+            if let Some(memory) = memory {
+                write!(
+                    out,
+                    " | {:>10} | {:>10} | {:>8}",
+                    format_bytes(memory.peak_bytes),
+                    format_bytes(memory.allocated_bytes),
+                    memory.allocations
+                )?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+}
+
+impl Comparison {
+    pub fn write(&self, mut out: impl Write, format: BenchFormat) -> Result<()> {
+        match format {
+            BenchFormat::Table => self.write_table(&mut out)?,
+            BenchFormat::Json => {
+                serde_json::to_writer_pretty(&mut out, self)
+                    .map_err(|err| AppError::Other(err.to_string()))?;
+                writeln!(out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Medians of both runs side by side
+    fn write_table(&self, mut out: impl Write) -> io::Result<()> {
+        let fmt = |ns: u64| format!("{:.2?}", Duration::from_nanos(ns));
+        let (single, multi) = (&self.single, &self.multi);
+        writeln!(
+            out,
+            "Day {}: median of {} iterations after {} warmup",
+            multi.day, multi.iterations, multi.warmup
+        )?;
+        writeln!(
+            out,
+            "{:<7} | {:>10} | {:>10} | {:>7}",
+            "Stage",
+            threads_label(single.threads),
+            threads_label(multi.threads),
+            "Speedup"
+        )?;
+        writeln!(
+            out,
+            "{}-+-{}-+-{}-+-{}",
+            "-".repeat(7),
+            "-".repeat(10),
+            "-".repeat(10),
+            "-".repeat(7)
+        )?;
+        let rows = std::iter::once(("parse".to_string(), &single.parse, &multi.parse)).chain(
+            single
+                .stars
+                .iter()
+                .zip(&multi.stars)
+                .map(|(s, m)| (format!("star {}", s.star), &s.solve, &m.solve)),
+        );
+        for (stage, single, multi) in rows {
+            let speedup = single.median_ns as f64 / multi.median_ns.max(1) as f64;
+            writeln!(
+                out,
+                "{stage:<7} | {:>10} | {:>10} | {:>6.2}x",
+                fmt(single.median_ns),
+                fmt(multi.median_ns),
+                speedup
+            )?;
+        }
+        Ok(())
+    }
+}
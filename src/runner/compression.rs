@@ -0,0 +1,149 @@
+//! Transparent decompression of gzip and zstd inputs
+
+use std::{
+    ffi::OsStr,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+use flate2::bufread::MultiGzDecoder;
+use ruzstd::decoding::{FrameDecoder, StreamingDecoder};
+use tracing::debug;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Detects the compression by the extension of `path`, then by the magic bytes at `start`
+    pub fn detect(path: &Path, start: &[u8]) -> Option<Self> {
+        match path.extension().and_then(OsStr::to_str) {
+            Some("gz") => Some(Self::Gzip),
+            Some("zst") => Some(Self::Zstd),
+            _ if start.starts_with(GZIP_MAGIC) => Some(Self::Gzip),
+            _ if start.starts_with(ZSTD_MAGIC) => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+
+/// Wraps `reader` in a streaming decoder when it is compressed, plain inputs are returned as is
+pub fn decompress(path: &Path, mut reader: Box<dyn BufRead>) -> io::Result<Box<dyn BufRead>> {
+    let compression = Compression::detect(path, reader.fill_buf()?);
+    debug!(compression = ?compression, "Input compression detected");
+    Ok(match compression {
+        Some(Compression::Gzip) => Box::new(BufReader::new(MultiGzDecoder::new(reader))),
+        Some(Compression::Zstd) => Box::new(BufReader::new(MultiZstdDecoder::new(reader)?)),
+        None => reader,
+    })
+}
+
+/// Zstd decoder reading every frame of the input, like [`MultiGzDecoder`] does for gzip members
+struct MultiZstdDecoder {
+    /// Frame being read, `None` once the input is exhausted
+    frame: Option<StreamingDecoder<Box<dyn BufRead>, FrameDecoder>>,
+}
+
+impl MultiZstdDecoder {
+    fn new(reader: Box<dyn BufRead>) -> io::Result<Self> {
+        Ok(Self {
+            frame: Some(zstd_frame(reader)?),
+        })
+    }
+}
+
+fn zstd_frame(
+    reader: Box<dyn BufRead>,
+) -> io::Result<StreamingDecoder<Box<dyn BufRead>, FrameDecoder>> {
+    StreamingDecoder::new(reader).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+impl Read for MultiZstdDecoder {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let Some(frame) = &mut self.frame else {
+                return Ok(0);
+            };
+            let read = frame.read(buf)?;
+            if read > 0 || buf.is_empty() {
+                return Ok(read);
+            }
+            // The frame is done, another one may follow it
+            let Some(mut reader) = self.frame.take().map(StreamingDecoder::into_inner) else {
+                return Ok(0);
+            };
+            if reader.fill_buf()?.is_empty() {
+                return Ok(0);
+            }
+            self.frame = Some(zstd_frame(reader)?);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Read, Write};
+
+    use flate2::{write::GzEncoder, Compression as GzLevel};
+    use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+
+    use super::*;
+
+    const TEXT: &str = "2333133121414131402\n";
+
+    fn read(path: &str, data: Vec<u8>) -> String {
+        let mut text = String::new();
+        decompress(Path::new(path), Box::new(Cursor::new(data)))
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        text
+    }
+
+    #[test]
+    fn detects_by_extension_and_magic() {
+        assert_eq!(
+            Compression::detect(Path::new("day9.txt.gz"), b""),
+            Some(Compression::Gzip)
+        );
+        assert_eq!(
+            Compression::detect(Path::new("-"), ZSTD_MAGIC),
+            Some(Compression::Zstd)
+        );
+        assert_eq!(Compression::detect(Path::new("day9.txt"), b"23331"), None);
+    }
+
+    #[test]
+    fn decompresses_while_reading() {
+        let mut gzip = GzEncoder::new(vec![], GzLevel::default());
+        gzip.write_all(TEXT.as_bytes()).unwrap();
+        let gzip = gzip.finish().unwrap();
+        let zstd = compress_to_vec(TEXT.as_bytes(), CompressionLevel::Fastest);
+
+        assert_eq!(read("day9.txt.gz", gzip.clone()), TEXT);
+        assert_eq!(read("day9.txt", gzip), TEXT);
+        assert_eq!(read("day9.zst", zstd.clone()), TEXT);
+        assert_eq!(read("-", zstd), TEXT);
+        assert_eq!(read("day9.txt", TEXT.into()), TEXT);
+    }
+
+    #[test]
+    fn reads_every_frame() {
+        let mut gzip = vec![];
+        let mut zstd = vec![];
+        for text in ["3   4\n4   3\n", "2   5\n"] {
+            let mut member = GzEncoder::new(vec![], GzLevel::default());
+            member.write_all(text.as_bytes()).unwrap();
+            gzip.extend(member.finish().unwrap());
+            zstd.extend(compress_to_vec(text.as_bytes(), CompressionLevel::Fastest));
+        }
+
+        assert_eq!(read("ab.txt.gz", gzip), "3   4\n4   3\n2   5\n");
+        assert_eq!(read("ab.zst", zstd), "3   4\n4   3\n2   5\n");
+    }
+}
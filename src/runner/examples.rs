@@ -0,0 +1,111 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{AppError, Result},
+    solutions::Star,
+};
+
+/// Example fixtures directory, relative to the crate root
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// File with per-example parameter tables, e.g. `[1]` followed by `blinks_one = 6`
+pub const PARAMS_FILE: &str = "params.toml";
+
+/// Example input with the expected answers from `star{N}.toml`
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub name: String,
+    pub input: PathBuf,
+    pub expected: BTreeMap<Star, String>,
+    /// Parameter overrides from [`PARAMS_FILE`]
+    pub params: Vec<(String, String)>,
+}
+
+/// Directory with the examples of a day
+pub fn day_dir(examples_dir: &Path, day: u8) -> PathBuf {
+    examples_dir.join(format!("day{day}"))
+}
+
+/// Reads a toml table, a missing file is `None`
+fn read_table(path: &Path) -> Result<Option<toml::Table>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(AppError::DataOpen(format!("{}: {err}", path.display())).into()),
+    };
+    toml::from_str(&content)
+        .map(Some)
+        .map_err(|err| AppError::DataParse(format!("{}: {err}", path.display()).into()).into())
+}
+
+/// Parameter overrides of every example in [`PARAMS_FILE`]
+fn load_params(dir: &Path) -> Result<BTreeMap<String, Vec<(String, String)>>> {
+    let path = dir.join(PARAMS_FILE);
+    let Some(table) = read_table(&path)? else {
+        return Ok(BTreeMap::new());
+    };
+    let invalid = |what: String| AppError::DataParse(format!("{}: {what}", path.display()).into());
+    table
+        .into_iter()
+        .map(|(name, params)| {
+            let toml::Value::Table(params) = params else {
+                return Err(invalid(format!("`{name}` is {params}, expected a table")).into());
+            };
+            let params = params
+                .into_iter()
+                .map(|(key, value)| match value {
+                    toml::Value::String(value) => Ok((key, value)),
+                    toml::Value::Integer(_) | toml::Value::Boolean(_) => {
+                        Ok((key, value.to_string()))
+                    }
+                    other => Err(invalid(format!("`{name}.{key}` is {other}"))),
+                })
+                .collect::<Result<_, AppError>>()?;
+            Ok((name, params))
+        })
+        .collect()
+}
+
+/// Loads every example that has an expected answer, missing directory has no examples
+pub fn load(examples_dir: &Path, day: u8) -> Result<Vec<Example>> {
+    let dir = day_dir(examples_dir, day);
+    let mut params = load_params(&dir)?;
+    let mut examples: BTreeMap<String, Example> = BTreeMap::new();
+    for star in Star::ALL {
+        let path = dir.join(format!("star{star}.toml"));
+        let Some(expected) = read_table(&path)? else {
+            continue;
+        };
+        for (name, answer) in expected {
+            let answer = match answer {
+                toml::Value::String(answer) => answer,
+                toml::Value::Integer(answer) => answer.to_string(),
+                other => {
+                    return Err(AppError::DataParse(
+                        format!(
+                            "{}: `{name}` is {other}, expected a string or an integer",
+                            path.display()
+                        )
+                        .into(),
+                    )
+                    .into())
+                }
+            };
+            examples
+                .entry(name.clone())
+                .or_insert_with(|| Example {
+                    input: dir.join(format!("{name}.txt")),
+                    params: params.remove(&name).unwrap_or_default(),
+                    name,
+                    expected: BTreeMap::new(),
+                })
+                .expected
+                .insert(star, answer);
+        }
+    }
+    Ok(examples.into_values().collect())
+}
@@ -0,0 +1,160 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Mutex, Once},
+    thread,
+    time::Duration,
+};
+
+use tracing::{warn, Span};
+
+use crate::error::{AppError, Result};
+
+/// Location of the last panic on a rayon worker, rayon re-raises worker panics on the caller
+static WORKER_LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+thread_local! {
+    /// Number of [`catch_panic`] calls in progress on this thread
+    static GUARDS: Cell<usize> = const { Cell::new(0) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Whether a panic on this thread ends up in [`catch_panic`]
+///
+/// Rayon workers only run solutions, which are always guarded on the thread that started them.
+fn guarded() -> bool {
+    GUARDS.with(Cell::get) > 0 || rayon::current_thread_index().is_some()
+}
+
+/// Wraps the current panic hook once, so that guarded panics only record their location
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location = info
+                .location()
+                .map_or_else(|| "unknown location".to_string(), ToString::to_string);
+            if !guarded() {
+                previous(info);
+            } else if GUARDS.with(Cell::get) > 0 {
+                LOCATION.with(|cell| *cell.borrow_mut() = Some(location));
+            } else {
+                *WORKER_LOCATION
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner()) = Some(location);
+            }
+        }));
+    });
+}
+
+fn take_location() -> String {
+    let worker = WORKER_LOCATION
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .take();
+    LOCATION
+        .with(|cell| cell.borrow_mut().take())
+        .or(worker)
+        .unwrap_or_else(|| "unknown location".to_string())
+}
+
+/// Turns a panic inside `f` into [`AppError::Panic`], without printing the panic report
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    install_hook();
+    take_location();
+    GUARDS.with(|guards| guards.set(guards.get() + 1));
+    let res = panic::catch_unwind(AssertUnwindSafe(f));
+    GUARDS.with(|guards| guards.set(guards.get() - 1));
+    res.unwrap_or_else(|payload| {
+        Err(AppError::Panic {
+            message: panic_message(&payload).to_string(),
+            location: take_location(),
+        }
+        .into())
+    })
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Runs `f` on its own thread and stops waiting for it after `timeout`
+///
+/// A timed out thread cannot be stopped, it keeps running in the background until it returns or
+/// the process exits.
+pub fn with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    let (tx, rx) = mpsc::channel();
+    let span = Span::current();
+    thread::Builder::new().name("solve".into()).spawn(move || {
+        let _ = tx.send(span.in_scope(|| catch_panic(f)));
+    })?;
+    match rx.recv_timeout(timeout) {
+        Ok(res) => res,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            warn!("Solution timed out, leaving it running in the background");
+            Err(AppError::Timeout(format!("no answer after {timeout:.2?}")).into())
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err(AppError::Other("Solution thread exited without an answer".into()).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn panic_carries_message_and_location() {
+        let line = line!() + 1;
+        let err = catch_panic::<()>(|| panic!("oops")).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with(&format!("Panicked at {}:{line}:", file!())),
+            "{err}"
+        );
+        assert!(err.to_string().ends_with(": oops"), "{err}");
+    }
+
+    #[test]
+    fn stops_waiting_after_timeout() {
+        let res = with_timeout(Duration::from_millis(10), || {
+            thread::sleep(Duration::from_secs(1));
+            Ok(())
+        });
+        assert!(res.unwrap_err().to_string().contains("Timed out"));
+        assert_eq!(with_timeout(Duration::from_secs(1), || Ok(4)).unwrap(), 4);
+    }
+
+    #[test]
+    fn timed_out_solution_does_not_guard_other_panics() {
+        let res = with_timeout(Duration::from_millis(10), || -> Result<()> {
+            thread::sleep(Duration::from_millis(50));
+            panic!("late");
+        });
+        assert!(res.unwrap_err().to_string().contains("Timed out"));
+        // The abandoned solution is still inside its guard on the background thread
+        assert!(!guarded());
+        assert!(catch_panic(|| Ok(guarded())).unwrap());
+
+        thread::sleep(Duration::from_millis(100));
+        let line = line!() + 1;
+        let err = catch_panic::<()>(|| panic!("oops")).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with(&format!("Panicked at {}:{line}:", file!())),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use std::io::{self, BufRead, Read};
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// 64-bit FNV-1a, identifies an input without storing it
+#[derive(Debug, Clone, Copy)]
+pub struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(FNV_OFFSET)
+    }
+}
+
+impl Fnv1a {
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Reader that hashes every byte consumed through it
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Fnv1a,
+}
+
+impl<R: BufRead> HashingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Fnv1a::default(),
+        }
+    }
+
+    /// Consumes the rest of the input and returns the hash of all of it
+    pub fn finish(mut self) -> io::Result<u64> {
+        io::copy(&mut self, &mut io::sink())?;
+        Ok(self.hasher.finish())
+    }
+}
+
+impl<R: BufRead> Read for HashingReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let buf = self.fill_buf()?;
+        let n = buf.len().min(out.len());
+        out[..n].copy_from_slice(&buf[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for HashingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt > 0 {
+            // Buffer was filled by the caller, so this does no I/O
+            if let Ok(buf) = self.inner.fill_buf() {
+                self.hasher.update(&buf[..amt]);
+            }
+        }
+        self.inner.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn fnv1a_reference_values() {
+        assert_eq!(Fnv1a::default().finish(), 0xcbf29ce484222325);
+        let mut hasher = Fnv1a::default();
+        hasher.update(b"a");
+        assert_eq!(hasher.finish(), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn reader_hashes_unread_rest() {
+        let mut reader = HashingReader::new(Cursor::new("125 17\n1 2\n"));
+        let first = (&mut reader).lines().next().unwrap().unwrap();
+        assert_eq!(first, "125 17");
+
+        let mut hasher = Fnv1a::default();
+        hasher.update(b"125 17\n1 2\n");
+        assert_eq!(reader.finish().unwrap(), hasher.finish());
+    }
+}
@@ -0,0 +1,146 @@
+//! Heap usage of a measured closure, counted by a global allocator behind the `count-alloc` feature
+//!
+//! Counters are process wide, so allocations of rayon workers and timed out solutions running in
+//! the background are included as well.
+
+use serde::Serialize;
+
+/// Heap usage while running a closure
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct MemoryStats {
+    /// Highest heap size above the size at the start
+    pub peak_bytes: u64,
+    /// Sum of every allocation, including the ones freed again
+    pub allocated_bytes: u64,
+    pub allocations: u64,
+}
+
+/// Human readable byte count, e.g. `1.50 MiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.2} {}", UNITS[unit])
+}
+
+#[cfg(feature = "count-alloc")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    use super::MemoryStats;
+
+    static CURRENT: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+    static TOTAL: AtomicU64 = AtomicU64::new(0);
+    static COUNT: AtomicU64 = AtomicU64::new(0);
+
+    /// System allocator that keeps track of the heap size
+    pub struct CountingAlloc;
+
+    impl CountingAlloc {
+        fn grow(size: usize) {
+            let current = CURRENT.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+            TOTAL.fetch_add(size as u64, Ordering::Relaxed);
+            COUNT.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn shrink(size: usize) {
+            CURRENT.fetch_sub(size as u64, Ordering::Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            Self::shrink(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                Self::shrink(layout.size());
+                Self::grow(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAlloc = CountingAlloc;
+
+    /// Runs `f` and counts its heap usage
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+        let start = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(start, Ordering::Relaxed);
+        let (total, count) = (TOTAL.load(Ordering::Relaxed), COUNT.load(Ordering::Relaxed));
+        let res = f();
+        let stats = MemoryStats {
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(start),
+            allocated_bytes: TOTAL.load(Ordering::Relaxed) - total,
+            allocations: COUNT.load(Ordering::Relaxed) - count,
+        };
+        (res, Some(stats))
+    }
+}
+
+#[cfg(feature = "count-alloc")]
+pub use counting::measure;
+
+/// Runs `f` and counts its heap usage, `None` without the `count-alloc` feature
+#[cfg(not(feature = "count-alloc"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    (f(), None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+
+    #[cfg(feature = "count-alloc")]
+    #[test]
+    fn counts_allocations() {
+        let (len, stats) = measure(|| {
+            let data = vec![0u8; 1 << 20];
+            std::hint::black_box(&data).len()
+        });
+        let stats = stats.unwrap();
+        assert_eq!(len, 1 << 20);
+        assert!(stats.peak_bytes >= 1 << 20);
+        assert!(stats.allocated_bytes >= 1 << 20);
+        assert!(stats.allocations >= 1);
+    }
+}
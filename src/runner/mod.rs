@@ -0,0 +1,254 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use tracing::{debug, instrument, trace};
+
+use crate::{
+    error::{AppError, ErrorKind, Report, Result},
+    solutions::{self, input::read_lines, Answer, Input, Params, Parsed, Puzzle, Star},
+};
+
+use guard::{catch_panic, with_timeout};
+use memory::MemoryStats;
+
+pub mod answers;
+pub mod bench;
+pub mod compression;
+pub mod examples;
+pub mod guard;
+pub mod hash;
+pub mod memory;
+pub mod output;
+pub mod table;
+pub mod watch;
+
+/// Last day of the advent calendar
+pub const LAST_DAY: u8 = 25;
+
+/// Data file path that reads the puzzle from stdin
+pub const STDIN_PATH: &str = "-";
+
+/// Sizes the global rayon pool, has to run before the first parallel iterator
+pub fn set_threads(threads: usize) -> Result<()> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|err| AppError::Other(format!("Cannot set up {threads} threads: {err}")).into())
+}
+
+/// Set by [`set_raw_input`]
+static RAW_INPUT: AtomicBool = AtomicBool::new(false);
+
+/// Passes inputs to solutions as read, without the [`Input`] normalization
+pub fn set_raw_input(raw: bool) {
+    RAW_INPUT.store(raw, Ordering::Relaxed);
+}
+
+/// Lines of `reader` as handed to solutions
+fn input<'a>(reader: impl BufRead + 'a) -> Input<'a> {
+    if RAW_INPUT.load(Ordering::Relaxed) {
+        Input::raw(read_lines(reader))
+    } else {
+        Input::new(read_lines(reader))
+    }
+}
+
+/// Conventional location of the puzzle input for a given day
+pub fn default_input(inputs_dir: &Path, day: u8) -> PathBuf {
+    inputs_dir.join(format!("day{day}.txt"))
+}
+
+/// Opens data file, or stdin for [`STDIN_PATH`], gzip and zstd inputs are decompressed while read
+pub fn open_input(path: &Path) -> Result<Box<dyn BufRead>> {
+    let open_error = |err: io::Error| AppError::DataOpen(format!("{}: {err}", path.display()));
+    let reader: Box<dyn BufRead> = if path.as_os_str() == STDIN_PATH {
+        trace!("Reading data from stdin");
+        Box::new(io::stdin().lock())
+    } else {
+        let file = File::open(path).map_err(open_error)?;
+        trace!(file = ?file, "Data file opened");
+        Box::new(BufReader::new(file))
+    };
+    Ok(compression::decompress(path, reader).map_err(open_error)?)
+}
+
+/// Puzzle input parsed by a [`Puzzle`]
+pub struct ParsedInput {
+    pub parsed: Parsed,
+    /// Time spent reading and parsing the input
+    pub time: Duration,
+    /// FNV-1a hash of the raw input
+    pub hash: u64,
+}
+
+/// Answer of a single star
+pub struct Solved {
+    pub answer: Result<Answer>,
+    /// Time spent inside the solution
+    pub time: Duration,
+    /// Heap usage of the solution, only with the `count-alloc` feature
+    pub memory: Option<MemoryStats>,
+}
+
+#[instrument(skip(puzzle), fields(day = puzzle.day()))]
+pub fn parse(puzzle: &dyn Puzzle, path: &Path) -> Result<ParsedInput> {
+    let start = Instant::now();
+    let mut reader = hash::HashingReader::new(open_input(path)?);
+    let parsed = catch_panic(|| puzzle.parse(input(&mut reader)))
+        .map_err(|err| err.with_parse_context(puzzle.day()))?;
+    let time = start.elapsed();
+    let hash = reader
+        .finish()
+        .map_err(|err| AppError::DataOpen(format!("{}: {err}", path.display())))?;
+    debug!(time = ?time, hash = hash, "Input parsed");
+    Ok(ParsedInput { parsed, time, hash })
+}
+
+/// Solves a star, panics become errors and `timeout` stops waiting for a slow solution
+///
+/// The timeout does not cover [`parse`], which runs on the calling thread.
+#[instrument(skip(puzzle, input), fields(day = puzzle.day()))]
+pub fn solve(
+    puzzle: &'static dyn Puzzle,
+    star: Star,
+    input: &ParsedInput,
+    params: &Params,
+    timeout: Option<Duration>,
+) -> Solved {
+    let start = Instant::now();
+    let (answer, memory) = memory::measure(|| match timeout {
+        Some(timeout) => {
+            let (parsed, params) = (input.parsed.clone(), params.clone());
+            with_timeout(timeout, move || puzzle.solve(star, &parsed, &params))
+        }
+        None => catch_panic(|| puzzle.solve(star, &input.parsed, params)),
+    });
+    let time = start.elapsed();
+    debug!(answer = ?answer, time = ?time, memory = ?memory, "Star solved");
+    Solved {
+        answer,
+        time,
+        memory,
+    }
+}
+
+/// Failure of a single day/star run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl From<&Report> for RunError {
+    fn from(err: &Report) -> Self {
+        Self {
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<AppError> for RunError {
+    fn from(err: AppError) -> Self {
+        Self {
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Outcome of a single day/star run
+#[derive(Debug)]
+pub struct RunReport {
+    pub day: u8,
+    pub star: Star,
+    pub answer: Result<Answer, RunError>,
+    /// Time spent reading and parsing the input
+    pub parse_time: Option<Duration>,
+    /// Time spent inside the solution
+    pub solve_time: Option<Duration>,
+    /// FNV-1a hash of the raw input
+    pub input_hash: Option<u64>,
+    /// Heap usage of the solution, only with the `count-alloc` feature
+    pub memory: Option<MemoryStats>,
+}
+
+impl RunReport {
+    fn failed(day: u8, star: Star, err: RunError) -> Self {
+        Self {
+            day,
+            star,
+            answer: Err(err),
+            parse_time: None,
+            solve_time: None,
+            input_hash: None,
+            memory: None,
+        }
+    }
+}
+
+/// Parses the input once and solves every given star, failures are reported per star
+pub fn run(
+    puzzle: &'static dyn Puzzle,
+    stars: &[Star],
+    path: &Path,
+    params: &Params,
+    timeout: Option<Duration>,
+) -> Vec<RunReport> {
+    let day = puzzle.day();
+    let input = match parse(puzzle, path) {
+        Ok(input) => input,
+        Err(err) => {
+            return stars
+                .iter()
+                .map(|&star| RunReport::failed(day, star, RunError::from(&err)))
+                .collect()
+        }
+    };
+
+    stars
+        .iter()
+        .map(|&star| {
+            let solved = solve(puzzle, star, &input, params, timeout);
+            RunReport {
+                day,
+                star,
+                answer: solved.answer.map_err(|err| RunError::from(&err)),
+                parse_time: Some(input.time),
+                solve_time: Some(solved.time),
+                input_hash: Some(input.hash),
+                memory: solved.memory,
+            }
+        })
+        .collect()
+}
+
+/// Runs both stars of every day with its default input and parameters
+pub fn run_all(inputs_dir: &Path, timeout: Option<Duration>) -> Vec<RunReport> {
+    (1..=LAST_DAY)
+        .flat_map(|day| match solutions::find(day) {
+            Some(puzzle) => run(
+                puzzle,
+                &Star::ALL,
+                &default_input(inputs_dir, day),
+                &Params::defaults(puzzle.params()),
+                timeout,
+            ),
+            None => Star::ALL
+                .map(|star| RunReport::failed(day, star, AppError::NotImplemented(day).into()))
+                .into(),
+        })
+        .collect()
+}
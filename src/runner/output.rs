@@ -0,0 +1,254 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::error::{AppError, ErrorKind, Report, Result};
+
+use super::{memory::MemoryStats, table, RunReport};
+
+/// Format of the run results written to stdout
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable answers
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Comma separated values with a header row
+    Csv,
+}
+
+/// Result of a single day/star as written in json and csv output
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub star: u8,
+    pub answer: Option<String>,
+    pub error: Option<&'a str>,
+    pub error_kind: Option<ErrorKind>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+    /// Hex FNV-1a hash of the raw input
+    pub input_hash: Option<String>,
+    /// Heap usage fields, only with the `count-alloc` feature
+    #[serde(flatten)]
+    pub memory: Option<MemoryStats>,
+}
+
+impl<'a> From<&'a RunReport> for Record<'a> {
+    fn from(report: &'a RunReport) -> Self {
+        Self {
+            day: report.day,
+            star: report.star as u8,
+            answer: report.answer.as_ref().ok().map(|a| a.to_string()),
+            error: report.answer.as_ref().err().map(|err| err.message.as_str()),
+            error_kind: report.answer.as_ref().err().map(|err| err.kind),
+            parse_ns: report.parse_time.map(|t| t.as_nanos() as u64),
+            solve_ns: report.solve_time.map(|t| t.as_nanos() as u64),
+            input_hash: report.input_hash.map(|h| format!("{h:016x}")),
+            memory: report.memory,
+        }
+    }
+}
+
+/// Error that stopped the whole command, written as the last json line
+#[derive(Debug, Serialize)]
+pub struct ErrorRecord {
+    pub error: String,
+    pub error_kind: ErrorKind,
+    pub exit_code: u8,
+}
+
+impl From<&Report> for ErrorRecord {
+    fn from(err: &Report) -> Self {
+        Self {
+            error: err.to_string(),
+            error_kind: err.kind(),
+            exit_code: err.kind().exit_code(),
+        }
+    }
+}
+
+const CSV_HEADER: [&str; 8] = [
+    "day",
+    "star",
+    "answer",
+    "error",
+    "error_kind",
+    "parse_ns",
+    "solve_ns",
+    "input_hash",
+];
+
+/// Extra csv columns when the reports carry heap usage
+const CSV_MEMORY_HEADER: [&str; 3] = ["peak_bytes", "allocated_bytes", "allocations"];
+
+/// Quotes a csv field when it contains a separator, quote or newline
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn write_csv(mut out: impl Write, reports: &[RunReport]) -> io::Result<()> {
+    let with_memory = reports.iter().any(|r| r.memory.is_some());
+    let mut header = CSV_HEADER.to_vec();
+    if with_memory {
+        header.extend(CSV_MEMORY_HEADER);
+    }
+    writeln!(out, "{}", header.join(","))?;
+    for report in reports {
+        let record = Record::from(report);
+        let mut fields = vec![
+            record.day.to_string(),
+            record.star.to_string(),
+            record.answer.unwrap_or_default(),
+            record.error.unwrap_or_default().to_string(),
+            record
+                .error_kind
+                .map(ErrorKind::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            record.parse_ns.map(|n| n.to_string()).unwrap_or_default(),
+            record.solve_ns.map(|n| n.to_string()).unwrap_or_default(),
+            record.input_hash.unwrap_or_default(),
+        ];
+        if with_memory {
+            fields.extend(match record.memory {
+                Some(memory) => [
+                    memory.peak_bytes.to_string(),
+                    memory.allocated_bytes.to_string(),
+                    memory.allocations.to_string(),
+                ],
+                None => Default::default(),
+            });
+        }
+        let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        writeln!(out, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+/// Writes reports in the given format, text is an aligned table
+pub fn write_reports(
+    mut out: impl Write,
+    format: OutputFormat,
+    reports: &[RunReport],
+) -> Result<()> {
+    match format {
+        OutputFormat::Text => table::write_table(&mut out, reports)?,
+        OutputFormat::Json => {
+            for report in reports {
+                serde_json::to_writer(&mut out, &Record::from(report))
+                    .map_err(|err| AppError::Other(err.to_string()))?;
+                writeln!(out)?;
+            }
+        }
+        OutputFormat::Csv => write_csv(&mut out, reports)?,
+    }
+    Ok(())
+}
+
+/// Writes the error as an [`ErrorRecord`] line, other formats leave errors to stderr
+pub fn write_error(mut out: impl Write, format: OutputFormat, err: &Report) -> Result<()> {
+    if format == OutputFormat::Json {
+        serde_json::to_writer(&mut out, &ErrorRecord::from(err))
+            .map_err(|err| AppError::Other(err.to_string()))?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{runner::RunError, solutions::Star};
+
+    use super::*;
+
+    fn reports() -> Vec<RunReport> {
+        vec![
+            RunReport {
+                day: 17,
+                star: Star::One,
+                answer: Ok("4,6,3".into()),
+                parse_time: Some(Duration::from_nanos(1500)),
+                solve_time: Some(Duration::from_nanos(200)),
+                input_hash: Some(0xaf63dc4c8601ec8c),
+                memory: None,
+            },
+            RunReport {
+                day: 17,
+                star: Star::Two,
+                answer: Err(RunError {
+                    kind: ErrorKind::Panic,
+                    message: "panicked: \"oops\"".into(),
+                }),
+                parse_time: None,
+                solve_time: None,
+                input_hash: None,
+                memory: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn json_is_one_record_per_line() {
+        let mut out = vec![];
+        write_reports(&mut out, OutputFormat::Json, &reports()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"day":17,"star":1,"answer":"4,6,3","error":null,"error_kind":null,"parse_ns":1500,"solve_ns":200,"input_hash":"af63dc4c8601ec8c"}"#
+        );
+        assert!(lines[1].contains(r#""error_kind":"panic""#), "{}", lines[1]);
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn json_error_record() {
+        let err: Report = AppError::DataOpen("inputs/day1.txt: not found".into()).into();
+        let mut out = vec![];
+        write_error(&mut out, OutputFormat::Json, &err).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap().trim_end(),
+            r#"{"error":"Data File Open Error: inputs/day1.txt: not found","error_kind":"data_open","exit_code":3}"#
+        );
+        let mut out = vec![];
+        write_error(&mut out, OutputFormat::Csv, &err).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn csv_quotes_fields() {
+        let mut out = vec![];
+        write_reports(&mut out, OutputFormat::Csv, &reports()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day,star,answer,error,error_kind,parse_ns,solve_ns,input_hash\n\
+             17,1,\"4,6,3\",,,1500,200,af63dc4c8601ec8c\n\
+             17,2,,\"panicked: \"\"oops\"\"\",panic,,,\n"
+        );
+    }
+
+    #[test]
+    fn memory_columns_only_when_counted() {
+        let mut reports = reports();
+        reports[0].memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            allocated_bytes: 4096,
+            allocations: 3,
+        });
+        let mut out = vec![];
+        write_reports(&mut out, OutputFormat::Csv, &reports).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(lines[0].ends_with(",input_hash,peak_bytes,allocated_bytes,allocations"));
+        assert!(lines[1].ends_with(",2048,4096,3"));
+        assert!(lines[2].ends_with(",,,"));
+    }
+}
@@ -0,0 +1,85 @@
+use std::{io::Write, time::Duration};
+
+use super::{memory::format_bytes, RunReport};
+
+const HEADER: [&str; 5] = ["Day", "Star", "Answer", "Parse", "Solve"];
+
+/// Extra columns when the reports carry heap usage
+const MEMORY_HEADER: [&str; 3] = ["Peak", "Allocated", "Allocs"];
+
+fn format_duration(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".to_string(), |d| format!("{d:.2?}"))
+}
+
+fn row(report: &RunReport, with_memory: bool) -> Vec<String> {
+    let mut row = vec![
+        report.day.to_string(),
+        report.star.to_string(),
+        match &report.answer {
+            Ok(answer) => answer.to_string(),
+            Err(err) => format!("error: {err}"),
+        },
+        format_duration(report.parse_time),
+        format_duration(report.solve_time),
+    ];
+    if with_memory {
+        row.extend(match report.memory {
+            Some(memory) => [
+                format_bytes(memory.peak_bytes),
+                format_bytes(memory.allocated_bytes),
+                memory.allocations.to_string(),
+            ],
+            None => ["-", "-", "-"].map(str::to_string),
+        });
+    }
+    row
+}
+
+/// Writes reports as an aligned text table
+pub fn write_table(mut out: impl Write, reports: &[RunReport]) -> std::io::Result<()> {
+    let with_memory = reports.iter().any(|r| r.memory.is_some());
+    let header: Vec<String> = HEADER
+        .iter()
+        .chain(if with_memory { &MEMORY_HEADER[..] } else { &[] })
+        .map(|s| s.to_string())
+        .collect();
+    let rows: Vec<Vec<String>> = reports.iter().map(|r| row(r, with_memory)).collect();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|r| r[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_row = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, &width))| match i {
+                // Answer and times are left aligned, numeric columns right aligned
+                2..=4 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
+
+    writeln!(out, "{}", format_row(&header).trim_end())?;
+    writeln!(
+        out,
+        "{}",
+        widths
+            .iter()
+            .map(|&w| "-".repeat(w))
+            .collect::<Vec<String>>()
+            .join("-+-")
+    )?;
+    for cells in rows.iter() {
+        writeln!(out, "{}", format_row(cells).trim_end())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use tracing::{debug, instrument};
+
+use crate::{
+    error::{AppError, Result},
+    solutions::{Params, Puzzle, Star},
+};
+
+use super::{examples, run, table, STDIN_PATH};
+
+/// How often watched files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification times and sizes of the watched files, `None` for missing ones
+///
+/// The size catches edits made within the resolution of the modification time.
+type Snapshot = Vec<(PathBuf, Option<(SystemTime, u64)>)>;
+
+fn snapshot(input: &Path, examples_dir: &Path) -> Snapshot {
+    let mut paths = vec![input.to_path_buf()];
+    if let Ok(dir) = fs::read_dir(examples_dir) {
+        paths.extend(dir.filter_map(|entry| entry.ok().map(|entry| entry.path())));
+    }
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let stamp = fs::metadata(&path)
+                .and_then(|m| Ok((m.modified()?, m.len())))
+                .ok();
+            (path, stamp)
+        })
+        .collect()
+}
+
+/// Re-runs the day on the input and its examples every time one of their files changes
+///
+/// Runs until interrupted, solver panics are shown as errors. `params` only apply to the input,
+/// examples use their own from `params.toml`.
+#[instrument(skip_all, fields(day = puzzle.day()))]
+pub fn watch(
+    puzzle: &'static dyn Puzzle,
+    stars: &[Star],
+    input: &Path,
+    params: &Params,
+    timeout: Option<Duration>,
+    examples_dir: &Path,
+) -> Result<()> {
+    if input.as_os_str() == STDIN_PATH {
+        return Err(AppError::DataOpen("Cannot watch stdin".into()).into());
+    }
+    let day_dir = examples::day_dir(examples_dir, puzzle.day());
+    let mut last = None;
+    loop {
+        let current = snapshot(input, &day_dir);
+        if last.as_ref() != Some(&current) {
+            debug!("Watched files changed");
+            let mut out = io::stdout().lock();
+            write!(out, "{CLEAR_SCREEN}")?;
+            write_run(
+                &mut out,
+                puzzle,
+                stars,
+                input,
+                params,
+                timeout,
+                examples_dir,
+            )?;
+            out.flush()?;
+            last = Some(current);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn write_run(
+    mut out: impl Write,
+    puzzle: &'static dyn Puzzle,
+    stars: &[Star],
+    input: &Path,
+    params: &Params,
+    timeout: Option<Duration>,
+    examples_dir: &Path,
+) -> Result<()> {
+    let day = puzzle.day();
+    writeln!(out, "Day {day}: {} ({})\n", puzzle.title(), input.display())?;
+    table::write_table(&mut out, &run(puzzle, stars, input, params, timeout))?;
+
+    let examples = match examples::load(examples_dir, day) {
+        Ok(examples) => examples,
+        Err(err) => {
+            writeln!(out, "\nExamples: error: {err}")?;
+            return Ok(());
+        }
+    };
+    if examples.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "\nExamples:")?;
+    for example in examples {
+        let example_stars: Vec<Star> = stars
+            .iter()
+            .copied()
+            .filter(|star| example.expected.contains_key(star))
+            .collect();
+        let reports = match Params::resolve(puzzle.params(), &example.params) {
+            Ok(params) => run(puzzle, &example_stars, &example.input, &params, timeout),
+            Err(err) => {
+                writeln!(out, "FAIL {}: {err}", example.name)?;
+                continue;
+            }
+        };
+        for report in reports {
+            let expected = &example.expected[&report.star];
+            let name = &example.name;
+            let star = report.star;
+            match &report.answer {
+                Ok(answer) if answer.to_string() == *expected => {
+                    writeln!(out, "ok   {name} star {star}: {answer}")?
+                }
+                Ok(answer) => writeln!(
+                    out,
+                    "FAIL {name} star {star}: {answer}, expected {expected}"
+                )?,
+                Err(err) => writeln!(out, "FAIL {name} star {star}: error: {err}")?,
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+
+    use crate::solutions;
+
+    use super::*;
+
+    #[test]
+    fn snapshot_changes_with_mtime_or_size() {
+        let dir = std::env::temp_dir().join(format!("advent_2024_watch_{}", std::process::id()));
+        let day_dir = dir.join("day1");
+        fs::create_dir_all(&day_dir).unwrap();
+        let input = dir.join("input.txt");
+        let example = day_dir.join("1.txt");
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let write = |path: &Path, text: &str, modified: SystemTime| {
+            fs::write(path, text).unwrap();
+            File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        };
+        write(&input, "3 4\n", time);
+        write(&example, "1 2\n", time);
+
+        let first = snapshot(&input, &day_dir);
+        assert_eq!(first.len(), 2);
+        assert_eq!(snapshot(&input, &day_dir), first);
+
+        write(&example, "1 2\n3 4\n", time);
+        let resized = snapshot(&input, &day_dir);
+        assert_ne!(resized, first);
+
+        write(&input, "3 5\n", time + Duration::from_secs(1));
+        let touched = snapshot(&input, &day_dir);
+        assert_ne!(touched, resized);
+
+        fs::remove_file(&input).unwrap();
+        assert_eq!(snapshot(&input, &day_dir)[1], (input.clone(), None));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn writes_run_and_examples() {
+        let puzzle = solutions::find(1).unwrap();
+        let input = Path::new("examples/day1/1.txt");
+        let params = Params::defaults(puzzle.params());
+        let mut out = vec![];
+        write_run(
+            &mut out,
+            puzzle,
+            &Star::ALL,
+            input,
+            &params,
+            None,
+            Path::new("examples"),
+        )
+        .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(
+            out.starts_with(&format!(
+                "Day 1: {} (examples/day1/1.txt)\n\n",
+                puzzle.title()
+            )),
+            "{out}"
+        );
+        assert!(
+            out.ends_with("\nExamples:\nok   1 star 1: 11\nok   1 star 2: 31\n"),
+            "{out}"
+        );
+    }
+}
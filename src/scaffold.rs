@@ -0,0 +1,181 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use tracing::{debug, instrument};
+
+use crate::{
+    error::{AppError, Result},
+    runner::examples::{self, EXAMPLES_DIR},
+};
+
+const TEMPLATE: &str = include_str!("solutions/template.rs");
+
+/// Solutions module, relative to the crate root
+const SOLUTIONS_DIR: &str = "src/solutions";
+
+/// Creates `dayN.rs` from the template, registers it and adds empty example fixtures
+///
+/// Returns created or modified files.
+#[instrument]
+pub fn new_day(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>> {
+    let solutions_dir = root.join(SOLUTIONS_DIR);
+    let mod_file = solutions_dir.join("mod.rs");
+    let day_file = solutions_dir.join(format!("day{day}.rs"));
+    if day_file.exists() {
+        return Err(AppError::Other(format!(
+            "Day {day} already exists at {}",
+            day_file.display()
+        ))
+        .into());
+    }
+    let module = read(&mod_file)?;
+
+    let module = register(&module, day)?;
+    let source = render(day, title)?;
+    write(&day_file, &source)?;
+    write(&mod_file, &module)?;
+    let mut created = vec![day_file, mod_file];
+
+    let examples_dir = examples::day_dir(&root.join(EXAMPLES_DIR), day);
+    fs::create_dir_all(&examples_dir)
+        .map_err(|err| AppError::DataOpen(format!("{}: {err}", examples_dir.display())))?;
+    for (name, content) in [
+        ("1.txt", ""),
+        ("star1.toml", "# example name = \"expected answer\"\n"),
+        ("star2.toml", "# example name = \"expected answer\"\n"),
+    ] {
+        let path = examples_dir.join(name);
+        if path.exists() {
+            debug!(path = ?path, "Fixture already exists");
+            continue;
+        }
+        write(&path, content)?;
+        created.push(path);
+    }
+    Ok(created)
+}
+
+/// Template with the day number and title filled in
+pub fn render(day: u8, title: &str) -> Result<String> {
+    edit_lines(TEMPLATE, |source| {
+        let source = source
+            .replace("\"Template\"", &format!("{title:?}"))
+            .replace("Template", &format!("Day{day}"));
+        let day_fn = "fn day(&self) -> u8 {\n        0\n";
+        if !source.contains(day_fn) {
+            return Err(AppError::Other(
+                "Template has no `fn day` returning 0".into(),
+            ));
+        }
+        Ok(source.replace(day_fn, &format!("fn day(&self) -> u8 {{\n        {day}\n")))
+    })
+}
+
+/// Adds `pub mod dayN;` and the registry entry to the solutions module
+pub fn register(module: &str, day: u8) -> Result<String> {
+    edit_lines(module, |module| {
+        let mod_line = format!("pub mod day{day};");
+        let entry = format!("    &day{day}::Day{day},");
+        let mut lines: Vec<String> = module.lines().map(str::to_string).collect();
+        if lines.contains(&mod_line) {
+            return Err(AppError::Other(format!("Day {day} is already registered")));
+        }
+
+        // Modules are sorted by name, so day10 goes before day2
+        let name = format!("day{day}");
+        let mods = lines.iter().enumerate().filter_map(|(i, line)| {
+            line.strip_prefix("pub mod ")
+                .and_then(|line| line.strip_suffix(';'))
+                .filter(|module| module.starts_with("day"))
+                .map(|module| (i, module))
+        });
+        let last_mod = mods.clone().next_back().map(|(i, _)| i);
+        let mod_at = mods
+            .clone()
+            .find(|(_, module)| *module > name.as_str())
+            .map(|(i, _)| i)
+            .or(last_mod.map(|i| i + 1))
+            .ok_or_else(|| AppError::Other("No `pub mod dayN;` declarations found".into()))?;
+        lines.insert(mod_at, mod_line);
+
+        // Registry is sorted by day number
+        let registry_start = lines
+            .iter()
+            .position(|line| line.starts_with("pub static REGISTRY"))
+            .ok_or_else(|| AppError::Other("No `REGISTRY` found".into()))?;
+        let entry_at = lines[registry_start..]
+            .iter()
+            .position(|line| {
+                line.trim() == "];"
+                    || line
+                        .trim()
+                        .strip_prefix("&day")
+                        .and_then(|rest| rest.split_once("::"))
+                        .and_then(|(n, _)| n.parse::<u8>().ok())
+                        .is_some_and(|n| n > day)
+            })
+            .map(|i| registry_start + i)
+            .ok_or_else(|| AppError::Other("Unterminated `REGISTRY`".into()))?;
+        lines.insert(entry_at, entry);
+
+        Ok(lines.join("\n") + "\n")
+    })
+}
+
+/// Runs `edit` on text with `\n` line endings, keeping `\r\n` of the original
+fn edit_lines(text: &str, edit: impl FnOnce(&str) -> Result<String, AppError>) -> Result<String> {
+    let crlf = text.contains("\r\n");
+    let edited = edit(&text.replace("\r\n", "\n"))?;
+    Ok(if crlf {
+        edited.replace('\n', "\r\n")
+    } else {
+        edited
+    })
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .map_err(|err| AppError::DataOpen(format!("{}: {err}", path.display())).into())
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content)
+        .map_err(|err| AppError::DataOpen(format!("{}: {err}", path.display())).into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MODULE: &str = "pub mod day1;\npub mod day10;\npub mod day2;\npub mod template;\n\npub static REGISTRY: &[&dyn Puzzle] = &[\n    &day1::Day1,\n    &day2::Day2,\n    &day10::Day10,\n];\n";
+
+    #[test]
+    fn register_keeps_order() {
+        let module = register(MODULE, 3).unwrap();
+        assert_eq!(
+            module,
+            "pub mod day1;\npub mod day10;\npub mod day2;\npub mod day3;\npub mod template;\n\npub static REGISTRY: &[&dyn Puzzle] = &[\n    &day1::Day1,\n    &day2::Day2,\n    &day3::Day3,\n    &day10::Day10,\n];\n"
+        );
+        assert!(register(&module, 3).is_err());
+    }
+
+    #[test]
+    fn register_keeps_crlf() {
+        let module = register(&MODULE.replace('\n', "\r\n"), 11).unwrap();
+        assert!(module.contains("pub mod day10;\r\npub mod day11;\r\npub mod day2;\r\n"));
+        assert!(module.contains("    &day10::Day10,\r\n    &day11::Day11,\r\n];\r\n"));
+    }
+
+    #[test]
+    fn render_fills_day_and_title() {
+        let source = render(22, "Monkey Market").unwrap();
+        assert!(source.contains("pub struct Day22;"));
+        assert!(source
+            .replace("\r\n", "\n")
+            .contains("fn day(&self) -> u8 {\n        22\n"));
+        assert!(source.contains("\"Monkey Market\""));
+        assert!(!source.contains("Template"));
+    }
+}
@@ -1,42 +1,71 @@
 use std::{
     cmp::Reverse,
     collections::{BinaryHeap, HashMap},
-    io,
 };
 
 use tracing::{debug, instrument, trace};
 
-use crate::error::{AppError, Result};
+use crate::error::Result;
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        calc_similarity_score(line_reader)?;
-    } else {
-        calc_distance(line_reader)?;
+use super::{Answer, Input, Params, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = LocationLists;
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Historian Hysteria"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<LocationLists> {
+        parse(input)
+    }
+
+    fn part_one(&self, lists: &LocationLists, _params: &Params) -> Result<Answer> {
+        Ok(calc_distance(lists)?.into())
+    }
+
+    fn part_two(&self, lists: &LocationLists, _params: &Params) -> Result<Answer> {
+        Ok(calc_similarity_score(lists)?.into())
     }
-    Ok(())
 }
 
-#[instrument(skip_all, ret)]
-fn calc_distance(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize> {
-    let mut left_heap = BinaryHeap::new();
-    let mut right_heap = BinaryHeap::new();
+#[derive(Debug, Default)]
+pub struct LocationLists {
+    left: Vec<usize>,
+    right: Vec<usize>,
+}
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<LocationLists> {
+    let mut lists = LocationLists::default();
 
     debug!("Parsing file");
-    for (line, i) in line_reader.zip(1..) {
+    for line in input.non_blank() {
         let line = line?;
-        let Some((left, right)) = line.split_once(char::is_whitespace) else {
-            return Err(AppError::DataParse(format!("on line: {i:?}")).into());
+        let [left, right] = line.integers::<usize>()?[..] else {
+            return Err(line.line_error("Expected two numbers").into());
         };
-        let left: usize = left.trim().parse()?;
-        let right: usize = right.trim().parse()?;
 
         trace!(left = left, right = right, "Parsed line");
 
-        left_heap.push(Reverse(left));
-        right_heap.push(Reverse(right));
+        lists.left.push(left);
+        lists.right.push(right);
     }
 
+    Ok(lists)
+}
+
+#[instrument(skip_all, ret)]
+fn calc_distance(lists: &LocationLists) -> Result<usize> {
+    let mut left_heap: BinaryHeap<_> = lists.left.iter().copied().map(Reverse).collect();
+    let mut right_heap: BinaryHeap<_> = lists.right.iter().copied().map(Reverse).collect();
+
     debug!("Calculating distance");
     let mut distance = 0;
     while let Some((Reverse(left), Reverse(right))) = left_heap.pop().zip(right_heap.pop()) {
@@ -49,22 +78,13 @@ fn calc_distance(line_reader: impl Iterator<Item = io::Result<String>>) -> Resul
 }
 
 #[instrument(skip_all, ret)]
-fn calc_similarity_score(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize> {
+fn calc_similarity_score(lists: &LocationLists) -> Result<usize> {
     let mut left_map = HashMap::new();
     let mut right_map = HashMap::new();
-
-    debug!("Parsing file");
-    for (line, i) in line_reader.zip(1..) {
-        let line = line?;
-        let Some((left, right)) = line.split_once(char::is_whitespace) else {
-            return Err(AppError::DataParse(format!("on line: {i:?}")).into());
-        };
-        let left: usize = left.trim().parse()?;
-        let right: usize = right.trim().parse()?;
-
-        trace!(left = left, right = right, "Parsed line");
-
+    for &left in lists.left.iter() {
         left_map.entry(left).and_modify(|c| *c += 1).or_insert(1);
+    }
+    for &right in lists.right.iter() {
         right_map.entry(right).and_modify(|c| *c += 1).or_insert(1);
     }
 
@@ -78,32 +98,3 @@ fn calc_similarity_score(line_reader: impl Iterator<Item = io::Result<String>>)
 
     Ok(similarity_score)
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"3   4
-            4   3
-            2   5
-            1   3
-            3   9
-            3   3"#;
-        let res = calc_distance(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 11);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"3   4
-            4   3
-            2   5
-            1   3
-            3   9
-            3   3"#;
-        let res = calc_similarity_score(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 31);
-    }
-}
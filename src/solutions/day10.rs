@@ -1,9 +1,5 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    io,
-};
+use std::collections::{HashSet, VecDeque};
 
-use itertools::Itertools;
 use rayon::prelude::*;
 
 use tracing::{instrument, trace};
@@ -13,28 +9,46 @@ use crate::{
     solutions::utils::{Coord, SIDE_MOVES},
 };
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        trailheads_ratting_sum(line_reader)?;
-    } else {
-        trailheads_score_sum(line_reader)?;
+use super::{Answer, Input, Params, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = TopographicMap;
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Hoof It"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<TopographicMap> {
+        Ok(parse(input)?)
+    }
+
+    fn part_one(&self, map: &TopographicMap, _params: &Params) -> Result<Answer> {
+        Ok(trailheads_score_sum(map)?.into())
+    }
+
+    fn part_two(&self, map: &TopographicMap, _params: &Params) -> Result<Answer> {
+        Ok(trailheads_ratting_sum(map)?.into())
     }
-    Ok(())
 }
 
-#[instrument(skip_all, ret)]
-fn trailheads_score_sum(
-    line_reader: impl Iterator<Item = io::Result<String>>,
-) -> Result<usize, AppError> {
-    let raw_blocks = line_reader
-        .map_ok(|s| {
-            s.chars()
-                .filter_map(|c| c.to_digit(10).map(|n| n as u8))
-                .collect::<Vec<u8>>()
-        })
-        .collect::<io::Result<Vec<Vec<u8>>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?;
+/// Heights by (row, col)
+pub type TopographicMap = Vec<Vec<u8>>;
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<TopographicMap, AppError> {
+    let raw_blocks = input.digit_grid()?;
     trace!(raw_blocks = ?raw_blocks);
+    Ok(raw_blocks)
+}
+
+#[instrument(skip_all, ret)]
+fn trailheads_score_sum(raw_blocks: &TopographicMap) -> Result<usize, AppError> {
     let count = raw_blocks
         .par_iter()
         .enumerate()
@@ -82,18 +96,7 @@ fn trailheads_score_sum(
 }
 
 #[instrument(skip_all, ret)]
-fn trailheads_ratting_sum(
-    line_reader: impl Iterator<Item = io::Result<String>>,
-) -> Result<usize, AppError> {
-    let raw_blocks = line_reader
-        .map_ok(|s| {
-            s.chars()
-                .filter_map(|c| c.to_digit(10).map(|n| n as u8))
-                .collect::<Vec<u8>>()
-        })
-        .collect::<io::Result<Vec<Vec<u8>>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?;
-    trace!(raw_blocks = ?raw_blocks);
+fn trailheads_ratting_sum(raw_blocks: &TopographicMap) -> Result<usize, AppError> {
     let count = raw_blocks
         .par_iter()
         .enumerate()
@@ -129,36 +132,3 @@ fn trailheads_ratting_sum(
 
     Ok(count)
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"89010123
-                      78121874
-                      87430965
-                      96549874
-                      45678903
-                      32019012
-                      01329801
-                      10456732"#;
-        let res = trailheads_score_sum(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 36);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"89010123
-                      78121874
-                      87430965
-                      96549874
-                      45678903
-                      32019012
-                      01329801
-                      10456732"#;
-        let res = trailheads_ratting_sum(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 81);
-    }
-}
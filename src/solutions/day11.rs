@@ -1,96 +1,100 @@
-use std::io;
-
 use cached::proc_macro::cached;
-use itertools::Itertools;
 
 use tracing::{instrument, trace, Level};
 
 use crate::error::{AppError, Result};
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        task_hard(line_reader)?;
-    } else {
-        task_simple(line_reader)?;
+use super::{Answer, Input, ParamSpec, ParamValue, Params, Solution};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Stones;
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Plutonian Pebbles"
+    }
+
+    fn params(&self) -> &'static [ParamSpec] {
+        &[
+            ParamSpec {
+                name: "blinks_one",
+                help: "Blinks of the first star",
+                default: ParamValue::Unsigned(25),
+            },
+            ParamSpec {
+                name: "blinks_two",
+                help: "Blinks of the second star",
+                default: ParamValue::Unsigned(75),
+            },
+        ]
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Stones> {
+        parse(input)
+    }
+
+    fn part_one(&self, stones: &Stones, params: &Params) -> Result<Answer> {
+        Ok(task_simple(stones, params.unsigned("blinks_one")? as usize)?.into())
+    }
+
+    fn part_two(&self, stones: &Stones, params: &Params) -> Result<Answer> {
+        Ok(task_hard(stones, params.unsigned("blinks_two")? as usize)?.into())
     }
-    Ok(())
+}
+
+/// Engraved numbers
+pub type Stones = Vec<usize>;
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Stones> {
+    let stones: Vec<usize> = input.integers()?.into_iter().flatten().collect();
+    trace!(input = ?stones);
+    Ok(stones)
 }
 
 #[instrument(ret(level = Level::TRACE))]
 #[cached]
-fn stone_count_after_steps(stone: usize, steps: usize) -> usize {
+fn stone_count_after_steps(stone: usize, steps: usize) -> Option<usize> {
     if steps == 0 {
-        return 1;
+        return Some(1);
     }
     if stone == 0 {
         return stone_count_after_steps(1, steps - 1);
     }
     let digits = stone.to_string();
-    if digits.len() % 2 == 0 {
+    if digits.len().is_multiple_of(2) {
         let (first, second) = digits.split_at(digits.len() / 2);
-        return stone_count_after_steps(first.parse().unwrap(), steps - 1)
-            + stone_count_after_steps(second.parse().unwrap(), steps - 1);
+        return stone_count_after_steps(first.parse().unwrap(), steps - 1)?
+            .checked_add(stone_count_after_steps(second.parse().unwrap(), steps - 1)?);
     }
     stone_count_after_steps(stone * 2024, steps - 1)
 }
 
+/// `None` once the count no longer fits into `usize`
 #[instrument(ret(level = Level::TRACE))]
-fn stones_count_after_steps(stones: Vec<usize>, steps: usize) -> usize {
-    stones
-        .into_iter()
-        .map(|stone| stone_count_after_steps(stone, steps))
-        .sum()
+fn stones_count_after_steps(stones: Vec<usize>, steps: usize) -> Option<usize> {
+    stones.into_iter().try_fold(0usize, |count, stone| {
+        count.checked_add(stone_count_after_steps(stone, steps)?)
+    })
 }
 
-#[instrument(skip_all, ret)]
-fn task_simple(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize, AppError> {
-    let stones: Vec<usize> = line_reader
-        .map_ok(|s| {
-            s.split(char::is_whitespace)
-                .filter_map(|n| n.parse().ok())
-                .collect::<Vec<usize>>()
-        })
-        .flatten()
-        .flatten()
-        .collect();
-    trace!(input = ?stones);
-    let stones = stones_count_after_steps(stones, 25);
-
-    Ok(stones)
+fn count_after_blinks(stones: &Stones, blinks: usize) -> Result<usize, AppError> {
+    stones_count_after_steps(stones.clone(), blinks).ok_or_else(|| {
+        AppError::LogicalError(format!("Stone count overflows after {blinks} blinks"))
+    })
 }
 
 #[instrument(skip_all, ret)]
-fn task_hard(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize, AppError> {
-    let stones: Vec<usize> = line_reader
-        .map_ok(|s| {
-            s.split(char::is_whitespace)
-                .filter_map(|n| n.parse().ok())
-                .collect::<Vec<usize>>()
-        })
-        .flatten()
-        .flatten()
-        .collect();
-    trace!(input = ?stones);
-    let stones = stones_count_after_steps(stones, 75);
-
-    Ok(stones)
+fn task_simple(stones: &Stones, blinks: usize) -> Result<usize, AppError> {
+    count_after_blinks(stones, blinks)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"125 17"#;
-        let res = task_simple(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 55312);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"125 17"#;
-        let res = task_hard(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 65601038650482);
-    }
+#[instrument(skip_all, ret)]
+fn task_hard(stones: &Stones, blinks: usize) -> Result<usize, AppError> {
+    count_after_blinks(stones, blinks)
 }
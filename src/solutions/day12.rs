@@ -1,7 +1,4 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    io,
-};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use itertools::Itertools;
 // use rayon::prelude::*;
@@ -13,17 +10,44 @@ use crate::{
     solutions::utils::{Direction, SIDE_MOVES},
 };
 
-use super::utils::Coord;
+use super::{utils::Coord, Answer, Input, Params, Solution};
 
 type Sides = (Direction, (usize, usize));
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        task_hard(line_reader)?;
-    } else {
-        task_simple(line_reader)?;
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = Garden;
+
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Garden Groups"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Garden> {
+        Ok(parse(input)?)
+    }
+
+    fn part_one(&self, garden: &Garden, _params: &Params) -> Result<Answer> {
+        Ok(task_simple(garden)?.into())
     }
-    Ok(())
+
+    fn part_two(&self, garden: &Garden, _params: &Params) -> Result<Answer> {
+        Ok(task_hard(garden)?.into())
+    }
+}
+
+/// Plant types by (row, col)
+pub type Garden = Vec<Vec<char>>;
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Garden, AppError> {
+    let raw = input.char_grid()?;
+    trace!(raw_input = ?raw);
+    Ok(raw)
 }
 
 #[instrument(skip(map), ret(level = Level::TRACE))]
@@ -96,13 +120,7 @@ fn count_sides(sides: &HashSet<Sides>) -> usize {
 }
 
 #[instrument(skip_all, ret)]
-fn task_simple(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize, AppError> {
-    let raw = line_reader
-        .map_ok(|s| s.chars().collect::<Vec<char>>())
-        .collect::<io::Result<Vec<Vec<char>>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?;
-    trace!(raw_input = ?raw);
-
+fn task_simple(raw: &Garden) -> Result<usize, AppError> {
     let mut visited = HashSet::new();
     let mut sum = 0;
     for (row_i, row) in raw.iter().enumerate() {
@@ -111,7 +129,7 @@ fn task_simple(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<
             if visited.contains(&coord) {
                 continue;
             }
-            let (sides, visits) = region_data(&raw, *ch, coord);
+            let (sides, visits) = region_data(raw, *ch, coord);
             sum += sides.len() * visits.len();
             visited.extend(visits);
         }
@@ -121,13 +139,7 @@ fn task_simple(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<
 }
 
 #[instrument(skip_all, ret)]
-fn task_hard(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize, AppError> {
-    let raw = line_reader
-        .map_ok(|s| s.chars().collect::<Vec<char>>())
-        .collect::<io::Result<Vec<Vec<char>>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?;
-    trace!(raw_input = ?raw);
-
+fn task_hard(raw: &Garden) -> Result<usize, AppError> {
     let mut visited = HashSet::new();
     let mut sum = 0;
     for (row_i, row) in raw.iter().enumerate() {
@@ -136,7 +148,7 @@ fn task_hard(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<us
             if visited.contains(&coord) {
                 continue;
             }
-            let (sides, visits) = region_data(&raw, *ch, coord);
+            let (sides, visits) = region_data(raw, *ch, coord);
             sum += count_sides(&sides) * visits.len();
             visited.extend(visits);
         }
@@ -144,40 +156,3 @@ fn task_hard(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<us
 
     Ok(sum)
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"RRRRIICCFF
-                      RRRRIICCCF
-                      VVRRRCCFFF
-                      VVRCCCJFFF
-                      VVVVCJJCFE
-                      VVIVCCJJEE
-                      VVIIICJJEE
-                      MIIIIIJJEE
-                      MIIISIJEEE
-                      MMMISSJEEE"#;
-        let res = task_simple(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 1930);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"RRRRIICCFF
-                      RRRRIICCCF
-                      VVRRRCCFFF
-                      VVRCCCJFFF
-                      VVVVCJJCFE
-                      VVIVCCJJEE
-                      VVIIICJJEE
-                      MIIIIIJJEE
-                      MIIISIJEEE
-                      MMMISSJEEE"#;
-        let res = task_hard(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 1206);
-    }
-}
@@ -1,14 +1,13 @@
-use std::io;
-
-use itertools::Itertools;
 use rayon::prelude::*;
 
 use tracing::{instrument, trace, Level};
 
 use crate::error::{AppError, Result};
 
-#[derive(Debug)]
-struct Machine {
+use super::{Answer, Input, Line, ParamSpec, ParamValue, Params, Solution};
+
+#[derive(Debug, Clone)]
+pub struct Machine {
     /// 3 tokens
     a_diff: (usize, usize),
     /// 1 token
@@ -52,15 +51,42 @@ impl Machine {
     }
 }
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        task_hard(line_reader)?;
-    } else {
-        task_simple(line_reader)?;
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Machines;
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Claw Contraption"
+    }
+
+    fn params(&self) -> &'static [ParamSpec] {
+        &[ParamSpec {
+            name: "offset",
+            help: "Added to both prize coordinates in the second star",
+            default: ParamValue::Unsigned(10000000000000),
+        }]
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Machines> {
+        Ok(parse(input)?)
+    }
+
+    fn part_one(&self, machines: &Machines, _params: &Params) -> Result<Answer> {
+        Ok(task_simple(machines)?.into())
+    }
+
+    fn part_two(&self, machines: &Machines, params: &Params) -> Result<Answer> {
+        Ok(task_hard(machines, params.unsigned("offset")? as usize)?.into())
     }
-    Ok(())
 }
 
+pub type Machines = Vec<Machine>;
+
 fn parse_line_into_usize_tuple(line: &str) -> Option<(usize, usize)> {
     line.split_once(',').and_then(|(left, right)| {
         left.chars()
@@ -79,125 +105,48 @@ fn parse_line_into_usize_tuple(line: &str) -> Option<(usize, usize)> {
     })
 }
 
-#[instrument(skip_all, ret)]
-fn task_simple(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize, AppError> {
-    let machines = &line_reader
-        .chunks(4)
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Machines, AppError> {
+    let machines = input
+        .blocks()?
         .into_iter()
-        .map(|chunk| {
-            chunk
-                .filter_ok(|s| !s.is_empty())
-                .collect::<io::Result<Vec<String>>>()
-        })
-        .map_ok(|lines| {
-            assert_eq!(lines.len(), 3);
-            let a_diff = parse_line_into_usize_tuple(&lines[0]).unwrap();
-            let b_diff = parse_line_into_usize_tuple(&lines[1]).unwrap();
-            let target = parse_line_into_usize_tuple(&lines[2]).unwrap();
-            Machine {
-                a_diff,
-                b_diff,
-                target,
-            }
+        .map(|lines| {
+            let [a, b, prize] = &lines[..] else {
+                return Err(lines[0].line_error(format!(
+                    "Expected a machine of 3 lines, got {}",
+                    lines.len()
+                )));
+            };
+            let tuple = |line: &Line| {
+                parse_line_into_usize_tuple(&line.text)
+                    .ok_or_else(|| line.line_error("Expected two numbers like `X+94, Y+34`"))
+            };
+            Ok(Machine {
+                a_diff: tuple(a)?,
+                b_diff: tuple(b)?,
+                target: tuple(prize)?,
+            })
         })
-        .collect::<io::Result<Vec<Machine>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?;
+        .collect::<Result<Machines, AppError>>()?;
     trace!(machines = ?machines, count = machines.len());
+    Ok(machines)
+}
 
-    let sum = machines
-        .into_par_iter()
-        .filter_map(Machine::min_tokens)
-        .sum();
+#[instrument(skip_all, ret)]
+fn task_simple(machines: &Machines) -> Result<usize, AppError> {
+    let sum = machines.par_iter().filter_map(Machine::min_tokens).sum();
 
     Ok(sum)
 }
 
 #[instrument(skip_all, ret)]
-fn task_hard(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize, AppError> {
-    let machines = &line_reader
-        .chunks(4)
-        .into_iter()
-        .map(|chunk| {
-            chunk
-                .filter_ok(|s| !s.is_empty())
-                .collect::<io::Result<Vec<String>>>()
-        })
-        .map_ok(|lines| {
-            assert_eq!(lines.len(), 3);
-            let a_diff = parse_line_into_usize_tuple(&lines[0]).unwrap();
-            let b_diff = parse_line_into_usize_tuple(&lines[1]).unwrap();
-            let target = parse_line_into_usize_tuple(&lines[2]).unwrap();
-            Machine {
-                a_diff,
-                b_diff,
-                target,
-            }
-        })
-        .map_ok(|m| m.fix_target_coord(10000000000000))
-        .collect::<io::Result<Vec<Machine>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?;
-    trace!(machines = ?machines, count = machines.len());
-
+fn task_hard(machines: &Machines, offset: usize) -> Result<usize, AppError> {
     let sum = machines
-        .into_par_iter()
-        .filter_map(Machine::min_tokens)
+        .par_iter()
+        .cloned()
+        .map(|m| m.fix_target_coord(offset))
+        .filter_map(|m| m.min_tokens())
         .sum();
 
     Ok(sum)
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"Button A: X+94, Y+34
-                      Button B: X+22, Y+67
-                      Prize: X=8400, Y=5400
-
-                      Button A: X+26, Y+66
-                      Button B: X+67, Y+21
-                      Prize: X=12748, Y=12176
-
-                      Button A: X+17, Y+86
-                      Button B: X+84, Y+37
-                      Prize: X=7870, Y=6450
-
-                      Button A: X+69, Y+23
-                      Button B: X+27, Y+71
-                      Prize: X=18641, Y=10279"#;
-        let res = task_simple(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 480);
-    }
-
-    #[test]
-    fn validate_failed_case_1() {
-        let data = r#"Button A: X+21, Y+30
-                      Button B: X+87, Y+34
-                      Prize: X=4413, Y=1790"#;
-        let res = task_simple(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 59);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"Button A: X+94, Y+34
-                      Button B: X+22, Y+67
-                      Prize: X=8400, Y=5400
-
-                      Button A: X+26, Y+66
-                      Button B: X+67, Y+21
-                      Prize: X=12748, Y=12176
-
-                      Button A: X+17, Y+86
-                      Button B: X+84, Y+37
-                      Prize: X=7870, Y=6450
-
-                      Button A: X+69, Y+23
-                      Button B: X+27, Y+71
-                      Prize: X=18641, Y=10279"#;
-        let res = task_hard(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 875318608908);
-    }
-}
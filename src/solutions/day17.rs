@@ -1,58 +1,42 @@
-use std::io;
-
-use itertools::Itertools;
 // use rayon::prelude::*;
 
 use tracing::{instrument, trace};
 
 use crate::error::{AppError, Result};
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        task_hard(line_reader)?;
-    } else {
-        task_simple(line_reader)?;
-    }
-    Ok(())
-}
+use super::{Answer, Input, Line, Params, Solution};
 
-#[instrument(skip_all, ret)]
-fn task_simple(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize, AppError> {
-    let raw = line_reader
-        .map_ok(|s| {
-            s.chars()
-                .filter_map(|c| c.to_digit(10).map(|n| n as u8))
-                .collect::<Vec<u8>>()
-        })
-        .collect::<io::Result<Vec<Vec<u8>>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?;
-    trace!(raw_input = ?raw);
+pub struct Day17;
 
-    Ok(0)
-}
+impl Solution for Day17 {
+    type Parsed = Raw;
 
-#[instrument(skip_all, ret)]
-fn task_hard(_line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize, AppError> {
-    unimplemented!()
-}
+    fn day(&self) -> u8 {
+        17
+    }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    fn title(&self) -> &'static str {
+        "Chronospatial Computer"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Raw> {
+        Ok(parse(input)?)
+    }
 
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"multiline
-                      datadatad"#;
-        let res = task_simple(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 0);
+    fn part_one(&self, _raw: &Raw, _params: &Params) -> Result<Answer> {
+        Err(AppError::NotImplemented(self.day()).into())
     }
 
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"multiline
-                      datadatad"#;
-        let res = task_hard(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 0);
+    fn part_two(&self, _raw: &Raw, _params: &Params) -> Result<Answer> {
+        Err(AppError::NotImplemented(self.day()).into())
     }
 }
+
+pub type Raw = Vec<Line>;
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Raw, AppError> {
+    let raw = input.lines()?;
+    trace!(raw_input = ?raw);
+    Ok(raw)
+}
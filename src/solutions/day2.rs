@@ -1,9 +1,11 @@
-use std::{io, mem};
+use std::mem;
 
 use tracing::{debug, instrument, Level};
 
 use crate::error::Result;
 
+use super::{Answer, Input, Params, Solution};
+
 #[derive(Debug, Default, Clone, Copy)]
 enum Tolerance {
     #[default]
@@ -35,7 +37,7 @@ impl SafetyReportAcc {
             Tolerance::NotAvailable | Tolerance::InProgress => None,
             Tolerance::Available => {
                 self.tolerance = Tolerance::InProgress;
-                let start = self.real_history.len().checked_sub(4).unwrap_or_default();
+                let start = self.real_history.len().saturating_sub(4);
                 for i in (start..self.real_history.len()).rev() {
                     let mut report = self.clone();
                     report.history = report.real_history.clone();
@@ -83,27 +85,50 @@ impl SafetyReportAcc {
     }
 }
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        count_safe_reports_tolerated(line_reader)?;
-    } else {
-        count_safe_reports(line_reader)?;
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Reports;
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Red-Nosed Reports"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Reports> {
+        parse(input)
+    }
+
+    fn part_one(&self, reports: &Reports, _params: &Params) -> Result<Answer> {
+        Ok(count_safe_reports(reports)?.into())
+    }
+
+    fn part_two(&self, reports: &Reports, _params: &Params) -> Result<Answer> {
+        Ok(count_safe_reports_tolerated(reports)?.into())
     }
+}
 
-    Ok(())
+/// Levels of every report
+pub type Reports = Vec<Vec<usize>>;
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Reports> {
+    Ok(input.integers()?)
 }
 
 #[instrument(skip_all, ret)]
-fn count_safe_reports(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize> {
+fn count_safe_reports(reports: &Reports) -> Result<usize> {
     let mut safe_count = 0;
 
-    for line in line_reader {
-        let line = line?;
-        let is_safe = line
-            .split(char::is_whitespace)
-            .filter_map(|s| s.parse::<usize>().ok())
+    for levels in reports.iter() {
+        let is_safe = levels
+            .iter()
+            .copied()
             .try_fold(SafetyReportAcc::default(), SafetyReportAcc::try_advance);
-        debug!(line = line, is_safe = ?is_safe, "Parsed line");
+        debug!(levels = ?levels, is_safe = ?is_safe, "Checked report");
         safe_count += is_safe.is_some() as usize;
     }
 
@@ -111,98 +136,17 @@ fn count_safe_reports(line_reader: impl Iterator<Item = io::Result<String>>) ->
 }
 
 #[instrument(skip_all, ret)]
-fn count_safe_reports_tolerated(
-    line_reader: impl Iterator<Item = io::Result<String>>,
-) -> Result<usize> {
+fn count_safe_reports_tolerated(reports: &Reports) -> Result<usize> {
     let mut safe_count = 0;
 
-    for line in line_reader {
-        let line = line?;
-        let is_safe = line
-            .split(char::is_whitespace)
-            .filter_map(|s| s.parse::<usize>().ok())
+    for levels in reports.iter() {
+        let is_safe = levels
+            .iter()
+            .copied()
             .try_fold(SafetyReportAcc::tolerated(), SafetyReportAcc::try_advance);
-        debug!(line = line, is_safe = ?is_safe, "Parsed line");
+        debug!(levels = ?levels, is_safe = ?is_safe, "Checked report");
         safe_count += is_safe.is_some() as usize;
     }
 
     Ok(safe_count)
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"7 6 4 2 1
-            1 2 7 8 9
-            9 7 6 2 1
-            1 3 2 4 5
-            8 6 4 4 1
-            1 3 6 7 9"#;
-        let res = count_safe_reports(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 2);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"7 6 4 2 1
-            1 2 7 8 9
-            9 7 6 2 1
-            1 3 2 4 5
-            8 6 4 4 1
-            1 3 6 7 9"#;
-        let res = count_safe_reports_tolerated(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 4);
-    }
-
-    #[test]
-    fn validate_second_star_custom_example_1() {
-        let data = r#"9 5 4 3 1"#;
-        let res = count_safe_reports_tolerated(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 1);
-    }
-
-    #[test]
-    fn validate_second_star_custom_example_2() {
-        let data = r#"4 6 4 3 1"#;
-        let res = count_safe_reports_tolerated(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 1);
-    }
-
-    #[test]
-    fn validate_second_star_custom_example_3() {
-        let data = r#"51 52 55 58 60 61 62 61"#;
-        let res = count_safe_reports_tolerated(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 1);
-    }
-
-    #[test]
-    fn validate_second_star_real_example_1() {
-        let data = r#"82 86 83 84 87"#;
-        let res = count_safe_reports_tolerated(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 1);
-    }
-
-    #[test]
-    fn validate_second_star_real_example_2() {
-        let data = r#"20 23 22 19 17 15"#;
-        let res = count_safe_reports_tolerated(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 1);
-    }
-
-    #[test]
-    fn validate_second_star_real_example_3() {
-        let data = r#"21 22 25 28 31 29 34"#;
-        let res = count_safe_reports_tolerated(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 1);
-    }
-
-    #[test]
-    fn validate_second_star_real_example_4() {
-        let data = r#"52 50 45 42 39"#;
-        let res = count_safe_reports_tolerated(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 0);
-    }
-}
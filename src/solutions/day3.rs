@@ -1,9 +1,11 @@
-use std::{io, str::Chars};
+use std::str::Chars;
 
 use tracing::{debug, instrument, Level};
 
 use crate::error::Result;
 
+use super::{Answer, Input, Params, Solution};
+
 #[derive(Debug)]
 struct Scanner {
     cond_enabled: bool,
@@ -116,22 +118,46 @@ impl<'a> Scanner {
     }
 }
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        scan_multiply_with_cond(line_reader)?;
-    } else {
-        scan_multiply(line_reader)?;
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Memory;
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Mull It Over"
     }
 
-    Ok(())
+    fn parse(&self, input: Input<'_>) -> Result<Memory> {
+        parse(input)
+    }
+
+    fn part_one(&self, memory: &Memory, _params: &Params) -> Result<Answer> {
+        Ok(scan_multiply(memory)?.into())
+    }
+
+    fn part_two(&self, memory: &Memory, _params: &Params) -> Result<Answer> {
+        Ok(scan_multiply_with_cond(memory)?.into())
+    }
+}
+
+/// Corrupted memory lines
+pub type Memory = Vec<String>;
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Memory> {
+    Ok(input.lines()?.into_iter().map(|line| line.text).collect())
 }
 
 #[instrument(skip_all, ret)]
-fn scan_multiply(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<isize> {
+fn scan_multiply(memory: &Memory) -> Result<isize> {
     let mut res = 0;
 
     let mut scanner = Scanner::default();
-    for line in line_reader.filter_map(Result::ok) {
+    for line in memory.iter() {
         let mut chars = line.chars();
         while let Some((left, right)) = scanner.scan_commands(chars.by_ref()) {
             res += left * right;
@@ -143,10 +169,10 @@ fn scan_multiply(line_reader: impl Iterator<Item = io::Result<String>>) -> Resul
 }
 
 #[instrument(skip_all, ret)]
-fn scan_multiply_with_cond(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<isize> {
+fn scan_multiply_with_cond(memory: &Memory) -> Result<isize> {
     let mut res = 0;
     let mut scanner = Scanner::default().with_cond();
-    for line in line_reader.filter_map(Result::ok) {
+    for line in memory.iter() {
         let mut chars = line.chars();
         while let Some((left, right)) = scanner.scan_commands(chars.by_ref()) {
             res += left * right;
@@ -156,22 +182,3 @@ fn scan_multiply_with_cond(line_reader: impl Iterator<Item = io::Result<String>>
 
     Ok(res)
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        let res = scan_multiply(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 161);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        let res = scan_multiply_with_cond(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 48);
-    }
-}
@@ -1,14 +1,15 @@
-use std::io;
-
 use strum::IntoEnumIterator;
 use tracing::{instrument, Level};
 
 use crate::error::Result;
 
-use super::utils::{Coord, Direction};
+use super::{
+    utils::{Coord, Direction},
+    Answer, Input, Params, Solution,
+};
 
 #[derive(Debug)]
-struct Scanner {
+pub struct Scanner {
     matrix: Vec<Vec<char>>,
 }
 
@@ -89,68 +90,43 @@ impl Scanner {
     }
 }
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        scan_mas_x(line_reader)?;
-    } else {
-        scan_xmas(line_reader)?;
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Scanner;
+
+    fn day(&self) -> u8 {
+        4
     }
-    Ok(())
-}
 
-#[instrument(skip_all, ret)]
-fn scan_xmas(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize> {
-    let mut matrix: Vec<Vec<char>> = vec![];
-    for line in line_reader.filter_map(Result::ok) {
-        matrix.push(line.chars().collect());
+    fn title(&self) -> &'static str {
+        "Ceres Search"
     }
-    let scanner = Scanner::new(matrix);
-    Ok(scanner.count_xmas())
-}
 
-#[instrument(skip_all, ret)]
-fn scan_mas_x(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize> {
-    let mut matrix: Vec<Vec<char>> = vec![];
-    for line in line_reader.filter_map(Result::ok) {
-        matrix.push(line.chars().collect());
+    fn parse(&self, input: Input<'_>) -> Result<Scanner> {
+        parse(input)
     }
-    let scanner = Scanner::new(matrix);
-    Ok(scanner.count_mas_x())
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"MMMSXXMASM
-            MSAMXMSMSA
-            AMXSXMAAMM
-            MSAMASMSMX
-            XMASAMXAMM
-            XXAMMXXAMA
-            SMSMSASXSS
-            SAXAMASAAA
-            MAMMMXMMMM
-            MXMXAXMASX"#;
-        let res = scan_xmas(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 18);
+    fn part_one(&self, scanner: &Scanner, _params: &Params) -> Result<Answer> {
+        Ok(scan_xmas(scanner)?.into())
     }
 
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#".M.S......
-            ..A..MSMS.
-            .M.S.MAA..
-            ..A.ASMSM.
-            .M.S.M....
-            ..........
-            S.S.S.S.S.
-            .A.A.A.A..
-            M.M.M.M.M.
-            .........."#;
-        let res = scan_mas_x(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 9);
+    fn part_two(&self, scanner: &Scanner, _params: &Params) -> Result<Answer> {
+        Ok(scan_mas_x(scanner)?.into())
     }
 }
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Scanner> {
+    Ok(Scanner::new(input.char_grid()?))
+}
+
+#[instrument(skip_all, ret)]
+fn scan_xmas(scanner: &Scanner) -> Result<usize> {
+    Ok(scanner.count_xmas())
+}
+
+#[instrument(skip_all, ret)]
+fn scan_mas_x(scanner: &Scanner) -> Result<usize> {
+    Ok(scanner.count_mas_x())
+}
@@ -1,12 +1,11 @@
-use std::{
-    collections::{HashMap, HashSet},
-    io,
-};
+use std::collections::{HashMap, HashSet};
 
 use tracing::{instrument, trace, Level};
 
 use crate::error::{AppError, Result};
 
+use super::{Answer, Input, Line, Params, Solution};
+
 #[derive(Debug)]
 struct Scanner {
     rules: [HashSet<usize>; 100],
@@ -62,60 +61,86 @@ impl Scanner {
     }
 }
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        scan_update_hard(line_reader)?;
-    } else {
-        scan_update(line_reader)?;
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = PrintQueue;
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Print Queue"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<PrintQueue> {
+        parse(input)
+    }
+
+    fn part_one(&self, queue: &PrintQueue, _params: &Params) -> Result<Answer> {
+        Ok(scan_update(queue)?.into())
+    }
+
+    fn part_two(&self, queue: &PrintQueue, _params: &Params) -> Result<Answer> {
+        Ok(scan_update_hard(queue)?.into())
     }
-    Ok(())
 }
 
-#[instrument(skip_all, ret)]
-fn scan_update(mut line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize> {
+#[derive(Debug)]
+pub struct PrintQueue {
+    scanner: Scanner,
+    updates: Vec<Vec<usize>>,
+}
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<PrintQueue> {
+    let mut blocks = input.blocks()?.into_iter();
     let mut scanner = Scanner::default();
-    for line in line_reader
-        .by_ref()
-        .filter_map(Result::ok)
-        .take_while(|s| !s.is_empty())
-    {
+    for line in blocks.next().unwrap_or_default() {
         let Some((left, right)) = line
+            .text
             .split_once('|')
             .and_then(|(left, right)| left.parse::<usize>().ok().zip(right.parse::<usize>().ok()))
         else {
-            return Err(AppError::DataParse(format!("on line: {line}")).into());
+            return Err(line.line_error("Expected a rule like `47|53`").into());
         };
+        check_pages(&line, &[left, right])?;
         scanner.add_rule(left, right);
     }
     trace!(scanner = ?scanner, "Rules parsed");
+    let mut updates = vec![];
+    for line in blocks.flatten() {
+        let pages = line.integers()?;
+        check_pages(&line, &pages)?;
+        updates.push(pages);
+    }
+    Ok(PrintQueue { scanner, updates })
+}
+
+/// Rules are indexed by page number
+fn check_pages(line: &Line, pages: &[usize]) -> Result<(), AppError> {
+    match pages.iter().find(|page| !(1..=100).contains(*page)) {
+        Some(page) => Err(line.line_error(format!("Page {page} is not between 1 and 100"))),
+        None => Ok(()),
+    }
+}
+
+#[instrument(skip_all, ret)]
+fn scan_update(queue: &PrintQueue) -> Result<usize> {
     let mut res = 0;
-    for line in line_reader.filter_map(Result::ok) {
-        let update: Vec<usize> = line.split(',').filter_map(|s| s.parse().ok()).collect();
-        res += scanner.verify_line(&update).unwrap_or(0);
+    for update in queue.updates.iter() {
+        res += queue.scanner.verify_line(update).unwrap_or(0);
     }
     Ok(res)
 }
 
 #[instrument(skip_all, ret)]
-fn scan_update_hard(mut line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize> {
-    let mut scanner = Scanner::default();
-    for line in line_reader
-        .by_ref()
-        .filter_map(Result::ok)
-        .take_while(|s| !s.is_empty())
-    {
-        let Some((left, right)) = line
-            .split_once('|')
-            .and_then(|(left, right)| left.parse::<usize>().ok().zip(right.parse::<usize>().ok()))
-        else {
-            return Err(AppError::DataParse(format!("on line: {line}")).into());
-        };
-        scanner.add_rule(left, right);
-    }
-    trace!(scanner = ?scanner, "Rules parsed");
+fn scan_update_hard(queue: &PrintQueue) -> Result<usize> {
+    let scanner = &queue.scanner;
     let mut res = 0;
-    for line in line_reader.filter_map(Result::ok) {
-        let mut update: Vec<usize> = line.split(',').filter_map(|s| s.parse().ok()).collect();
+    for update in queue.updates.iter() {
+        let mut update = update.clone();
         let mut failed_first = false;
         loop {
             match scanner.verify_line(&update) {
@@ -135,76 +160,3 @@ fn scan_update_hard(mut line_reader: impl Iterator<Item = io::Result<String>>) -
     }
     Ok(res)
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"47|53
-            97|13
-            97|61
-            97|47
-            75|29
-            61|13
-            75|53
-            29|13
-            97|29
-            53|29
-            61|53
-            97|53
-            61|29
-            47|13
-            75|47
-            97|75
-            47|61
-            75|61
-            47|29
-            75|13
-            53|13
-
-            75,47,61,53,29
-            97,61,53,29,13
-            75,29,13
-            75,97,47,61,53
-            61,13,29
-            97,13,75,29,47"#;
-        let res = scan_update(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 143);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"47|53
-            97|13
-            97|61
-            97|47
-            75|29
-            61|13
-            75|53
-            29|13
-            97|29
-            53|29
-            61|53
-            97|53
-            61|29
-            47|13
-            75|47
-            97|75
-            47|61
-            75|61
-            47|29
-            75|13
-            53|13
-
-            75,47,61,53,29
-            97,61,53,29,13
-            75,29,13
-            75,97,47,61,53
-            61,13,29
-            97,13,75,29,47"#;
-        let res = scan_update_hard(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 123);
-    }
-}
@@ -1,14 +1,14 @@
 use rayon::prelude::*;
-use std::{
-    fmt::{Display, Write},
-    io,
-};
+use std::fmt::{Display, Write};
 
 use tracing::{debug, instrument, trace, Level};
 
 use crate::error::{AppError, Result};
 
-use super::utils::{Coord, Direction};
+use super::{
+    utils::{Coord, Direction},
+    Answer, Input, Line, Params, Solution,
+};
 
 #[derive(Debug, Clone, Copy)]
 struct Guard {
@@ -44,7 +44,7 @@ impl TryFrom<&char> for Guard {
             'v' => Ok(Self {
                 direction: Direction::Bottom,
             }),
-            _ => Err(AppError::DataParse("Incorrect character".to_string())),
+            _ => Err(AppError::DataParse("Incorrect character".into())),
         }
     }
 }
@@ -95,7 +95,7 @@ impl Display for MapItem {
 }
 
 #[derive(Debug, Clone)]
-struct Scanner {
+pub struct Scanner {
     map: Vec<Vec<MapItem>>,
     guard: (Coord, Direction),
 }
@@ -112,33 +112,37 @@ impl Display for Scanner {
     }
 }
 
-impl TryFrom<Vec<Vec<char>>> for Scanner {
+impl TryFrom<Vec<Line>> for Scanner {
     type Error = AppError;
 
-    fn try_from(value: Vec<Vec<char>>) -> Result<Self, Self::Error> {
+    fn try_from(lines: Vec<Line>) -> Result<Self, Self::Error> {
         let mut guard: Option<((usize, usize), Direction)> = None;
-        let map = value
+        let map = lines
             .iter()
             .enumerate()
-            .map(|(row_i, row)| {
-                row.iter()
+            .map(|(row_i, line)| {
+                line.text
+                    .char_indices()
                     .enumerate()
-                    .map(|(col_i, ch)| match ch {
+                    .map(|(col_i, (offset, ch))| match ch {
                         '^' | '>' | '<' | 'v' => {
-                            let g: Guard = ch.try_into()?;
+                            let g: Guard = (&ch).try_into()?;
                             let dir: Direction = g.into();
                             guard = Some(((row_i, col_i), dir));
                             Ok(MapItem::Guard(g))
                         }
                         '.' => Ok(MapItem::Floor),
                         '#' => Ok(MapItem::Wall),
-                        _ => Err(AppError::DataParse(format!("Unknown character: {ch}"))),
+                        _ => Err(line.error(
+                            format!("Unknown character `{ch}`"),
+                            offset..offset + ch.len_utf8(),
+                        )),
                     })
                     .collect::<Result<Vec<MapItem>, AppError>>()
             })
             .collect::<Result<Vec<Vec<MapItem>>, AppError>>()?;
         let Some(guard) = guard else {
-            return Err(AppError::DataParse("Guard not found".to_string()));
+            return Err(AppError::DataParse("Guard not found".into()));
         };
         Ok(Self { map, guard })
     }
@@ -219,70 +223,45 @@ impl Scanner {
     }
 }
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        count_loop_options(line_reader)?;
-    } else {
-        count_guard_area(line_reader)?;
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = Scanner;
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Guard Gallivant"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Scanner> {
+        parse(input)
     }
-    Ok(())
+
+    fn part_one(&self, scanner: &Scanner, _params: &Params) -> Result<Answer> {
+        Ok(count_guard_area(scanner)?.into())
+    }
+
+    fn part_two(&self, scanner: &Scanner, _params: &Params) -> Result<Answer> {
+        Ok(count_loop_options(scanner)?.into())
+    }
+}
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Scanner> {
+    Ok(input.rectangular_lines()?.try_into()?)
 }
 
 #[instrument(skip_all, ret)]
-fn count_guard_area(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize> {
-    let mut map = vec![];
-    for line in line_reader.filter_map(Result::ok) {
-        map.push(line.chars().collect());
-    }
-    let scanner: Scanner = map.try_into()?;
+fn count_guard_area(scanner: &Scanner) -> Result<usize> {
     let scanner = scanner.plot_guard_route();
     debug!("success walk\n{scanner}");
     Ok(scanner.count_visited())
 }
 
 #[instrument(skip_all, ret)]
-fn count_loop_options(line_reader: impl Iterator<Item = io::Result<String>>) -> Result<usize> {
-    let mut map = vec![];
-    for line in line_reader.filter_map(Result::ok) {
-        map.push(line.chars().collect());
-    }
-    let scanner: Scanner = map.try_into()?;
+fn count_loop_options(scanner: &Scanner) -> Result<usize> {
     Ok(scanner.count_block_options())
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"....#.....
-                      .........#
-                      ..........
-                      ..#.......
-                      .......#..
-                      ..........
-                      .#..^.....
-                      ........#.
-                      #.........
-                      ......#..."#;
-        let res = count_guard_area(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 41);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"....#.....
-                      .........#
-                      ..........
-                      ..#.......
-                      .......#..
-                      ..........
-                      .#..^.....
-                      ........#.
-                      #.........
-                      ......#..."#;
-        let res = count_loop_options(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 6);
-    }
-}
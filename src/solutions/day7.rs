@@ -1,11 +1,12 @@
 use itertools::{repeat_n, Itertools};
 use rayon::prelude::*;
-use std::io;
 
 use tracing::{instrument, Level};
 
 use crate::error::{AppError, Result};
 
+use super::{Answer, Input, Line, Params, Solution};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Operator {
     Sum,
@@ -45,105 +46,78 @@ impl PermutationsCalculator {
     }
 }
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        try_combine_numbers_with_concat(line_reader)?;
-    } else {
-        try_combine_numbers(line_reader)?;
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed = Equations;
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Bridge Repair"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Equations> {
+        Ok(parse(input)?)
+    }
+
+    fn part_one(&self, equations: &Equations, _params: &Params) -> Result<Answer> {
+        Ok(try_combine_numbers(equations)?.into())
+    }
+
+    fn part_two(&self, equations: &Equations, _params: &Params) -> Result<Answer> {
+        Ok(try_combine_numbers_with_concat(equations)?.into())
     }
-    Ok(())
 }
 
-#[instrument(skip_all, ret)]
-fn try_combine_numbers(
-    line_reader: impl Iterator<Item = io::Result<String>>,
-) -> Result<usize, AppError> {
-    let Some(lines) = line_reader
-        .filter_map(Result::ok)
-        .filter_map(|s| {
-            s.split_once(':')
-                .map(|(t, nums)| (t.to_owned(), nums.trim().to_owned()))
-        })
-        .map(|(t, nums)| {
-            t.parse::<usize>().ok().zip(
-                nums.split(char::is_whitespace)
-                    .map(|n| n.parse().ok())
-                    .collect::<Option<Vec<usize>>>(),
-            )
-        })
-        .collect::<Option<Vec<(usize, Vec<usize>)>>>()
-    else {
-        return Err(AppError::DataParse("data corrupted".to_string()));
+/// (Target, Numbers)
+pub type Equations = Vec<(usize, Vec<usize>)>;
+
+/// Parses `target: n1 n2 ...`
+fn parse_equation(line: &Line) -> Result<(usize, Vec<usize>), AppError> {
+    let Some(colon) = line.text.find(':') else {
+        return Err(line.line_error("Expected `target: numbers`"));
     };
+    let target = line.text[..colon]
+        .parse()
+        .map_err(|_| line.error("Invalid target", 0..colon))?;
+    let nums = line
+        .tokens()
+        .filter(|(columns, _)| columns.start > colon)
+        .map(|(columns, n)| n.parse().map_err(|_| line.error("Invalid number", columns)))
+        .collect::<Result<Vec<usize>, AppError>>()?;
+    if nums.is_empty() {
+        return Err(line.error("Expected at least one number", colon..colon + 1));
+    }
+    Ok((target, nums))
+}
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Equations, AppError> {
+    input
+        .non_blank()
+        .map(|line| parse_equation(&line?))
+        .collect()
+}
+
+#[instrument(skip_all, ret)]
+fn try_combine_numbers(equations: &Equations) -> Result<usize, AppError> {
     let operations = vec![Operator::Sum, Operator::Multiply];
-    Ok(lines
-        .into_par_iter()
+    Ok(equations
+        .par_iter()
         .filter(|(target, nums)| PermutationsCalculator::can_be_solved(target, nums, &operations))
         .map(|(t, _)| t)
         .sum())
 }
 
 #[instrument(skip_all, ret)]
-fn try_combine_numbers_with_concat(
-    line_reader: impl Iterator<Item = io::Result<String>>,
-) -> Result<usize, AppError> {
-    let Some(lines) = line_reader
-        .filter_map(Result::ok)
-        .filter_map(|s| {
-            s.split_once(':')
-                .map(|(t, nums)| (t.to_owned(), nums.trim().to_owned()))
-        })
-        .map(|(t, nums)| {
-            t.parse::<usize>().ok().zip(
-                nums.split(char::is_whitespace)
-                    .map(|n| n.parse().ok())
-                    .collect::<Option<Vec<usize>>>(),
-            )
-        })
-        .collect::<Option<Vec<(usize, Vec<usize>)>>>()
-    else {
-        return Err(AppError::DataParse("data corrupted".to_string()));
-    };
+fn try_combine_numbers_with_concat(equations: &Equations) -> Result<usize, AppError> {
     let operations = vec![Operator::Sum, Operator::Multiply, Operator::Concat];
-    Ok(lines
-        .into_par_iter()
+    Ok(equations
+        .par_iter()
         .filter(|(target, nums)| PermutationsCalculator::can_be_solved(target, nums, &operations))
         .map(|(t, _)| t)
         .sum())
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"190: 10 19
-                      3267: 81 40 27
-                      83: 17 5
-                      156: 15 6
-                      7290: 6 8 6 15
-                      161011: 16 10 13
-                      192: 17 8 14
-                      21037: 9 7 18 13
-                      292: 11 6 16 20"#;
-        let res = try_combine_numbers(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 3749);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"190: 10 19
-                      3267: 81 40 27
-                      83: 17 5
-                      156: 15 6
-                      7290: 6 8 6 15
-                      161011: 16 10 13
-                      192: 17 8 14
-                      21037: 9 7 18 13
-                      292: 11 6 16 20"#;
-        let res =
-            try_combine_numbers_with_concat(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 11387);
-    }
-}
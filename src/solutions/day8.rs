@@ -1,9 +1,6 @@
 use itertools::Itertools;
 use rayon::prelude::*;
-use std::{
-    collections::{HashMap, HashSet},
-    io,
-};
+use std::collections::{HashMap, HashSet};
 
 use tracing::{instrument, trace, Level};
 
@@ -12,6 +9,8 @@ use crate::{
     solutions::utils::Coord,
 };
 
+use super::{Answer, Input, Params, Solution};
+
 #[instrument(ret(level = Level::TRACE))]
 fn calc_antinodes_coords(
     (f_row, f_col): &Coord,
@@ -54,23 +53,59 @@ fn calc_antinodes_coord((f_row, f_col): &Coord, (s_row, s_col): &Coord) -> Optio
     usize::try_from(n_row).ok().zip(usize::try_from(n_col).ok())
 }
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        count_antinodes_rec(line_reader)?;
-    } else {
-        count_antinodes(line_reader)?;
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = AntennaMap;
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Resonant Collinearity"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<AntennaMap> {
+        Ok(parse(input)?)
+    }
+
+    fn part_one(&self, map: &AntennaMap, _params: &Params) -> Result<Answer> {
+        Ok(count_antinodes(map)?.into())
+    }
+
+    fn part_two(&self, map: &AntennaMap, _params: &Params) -> Result<Answer> {
+        Ok(count_antinodes_rec(map)?.into())
     }
-    Ok(())
 }
 
-#[instrument(skip_all, ret)]
-fn count_antinodes(
-    line_reader: impl Iterator<Item = io::Result<String>>,
-) -> Result<usize, AppError> {
-    let mut char_map = line_reader
-        .map_ok(|s| s.chars().collect())
-        .collect::<io::Result<Vec<Vec<char>>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?;
+#[derive(Debug)]
+pub struct AntennaMap {
+    char_map: Vec<Vec<char>>,
+    stations: HashMap<char, HashSet<Coord>>,
+}
+
+impl AntennaMap {
+    fn render_antinodes(&self, antinodes: &[Coord]) -> String {
+        let mut char_map = self.char_map.clone();
+        for (row, col) in antinodes.iter() {
+            if char_map[*row][*col] == '.' {
+                char_map[*row][*col] = '#';
+            }
+        }
+
+        let mut map = String::with_capacity(char_map.len().pow(2) + char_map.len());
+        for row in char_map.iter() {
+            map.push_str(&row.iter().join(""));
+            map.push('\n');
+        }
+        map
+    }
+}
+
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<AntennaMap, AppError> {
+    let char_map = input.char_grid()?;
 
     let stations: HashMap<char, HashSet<Coord>> =
         char_map
@@ -93,7 +128,13 @@ fn count_antinodes(
 
     trace!(char_map = ?char_map, stations = ?stations, "Parsed");
 
-    let antinodes = stations
+    Ok(AntennaMap { char_map, stations })
+}
+
+#[instrument(skip_all, ret)]
+fn count_antinodes(map: &AntennaMap) -> Result<usize, AppError> {
+    let antinodes = map
+        .stations
         .par_iter()
         .flat_map(|(_l, coords)| {
             coords
@@ -106,77 +147,27 @@ fn count_antinodes(
                 })
                 .collect::<Vec<Coord>>()
         })
-        .filter(|(row, col)| row.max(col) < &char_map.len())
+        .filter(|(row, col)| row.max(col) < &map.char_map.len())
         .collect::<Vec<Coord>>()
         .into_iter()
         .unique()
         .collect_vec();
 
-    for (row, col) in antinodes.iter() {
-        if char_map[*row][*col] == '.' {
-            char_map[*row][*col] = '#';
-        }
-    }
-
-    let mut map = String::with_capacity(char_map.len().pow(2) + char_map.len());
-    for row in char_map.iter() {
-        map.push_str(&row.iter().join(""));
-        map.push('\n');
-    }
-
     trace!(antinodes = ?antinodes, "Antinodes calculated");
-    trace!("New map\n{map}");
+    trace!("New map\n{}", map.render_antinodes(&antinodes));
 
     Ok(antinodes.len())
 }
 
 #[instrument(skip_all, ret)]
-fn count_antinodes_rec(
-    line_reader: impl Iterator<Item = io::Result<String>>,
-) -> Result<usize, AppError> {
-    let mut char_map = line_reader
-        .map_ok(|s| s.chars().collect())
-        .collect::<io::Result<Vec<Vec<char>>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?;
-
-    let coords: HashSet<Coord> =
-        char_map
-            .iter()
-            .enumerate()
-            .fold(HashSet::new(), |mut acc, (row_i, row)| {
-                row.iter()
-                    .copied()
-                    .enumerate()
-                    .filter(|(_, ch)| ch != &'.')
-                    .for_each(|(col_i, _ch)| {
-                        acc.insert((row_i, col_i));
-                    });
-                acc
-            });
-
-    let stations: HashMap<char, HashSet<Coord>> =
-        char_map
-            .iter()
-            .enumerate()
-            .fold(HashMap::new(), |mut acc, (row_i, row)| {
-                row.iter()
-                    .copied()
-                    .enumerate()
-                    .filter(|(_, ch)| ch != &'.')
-                    .for_each(|(col_i, ch)| {
-                        acc.entry(ch)
-                            .and_modify(|s| {
-                                s.insert((row_i, col_i));
-                            })
-                            .or_insert(HashSet::from([(row_i, col_i)]));
-                    });
-                acc
-            });
+fn count_antinodes_rec(map: &AntennaMap) -> Result<usize, AppError> {
+    let coords: HashSet<Coord> = map.stations.values().flatten().copied().collect();
 
-    trace!(char_map = ?char_map, coords = ?coords, stations = ?stations, "Parsed");
+    trace!(coords = ?coords);
 
-    let bounds = char_map.len() as isize;
-    let antinodes = stations
+    let bounds = map.char_map.len() as isize;
+    let antinodes = map
+        .stations
         .par_iter()
         .flat_map(|(_l, coords)| {
             coords
@@ -197,62 +188,8 @@ fn count_antinodes_rec(
         .unique()
         .collect_vec();
 
-    for (row, col) in antinodes.iter() {
-        if char_map[*row][*col] == '.' {
-            char_map[*row][*col] = '#';
-        }
-    }
-
-    let mut map = String::with_capacity(char_map.len().pow(2) + char_map.len());
-    for row in char_map.iter() {
-        map.push_str(&row.iter().join(""));
-        map.push('\n');
-    }
-
     trace!(antinodes = ?antinodes, "Antinodes calculated");
-    trace!("New map\n{map}");
+    trace!("New map\n{}", map.render_antinodes(&antinodes));
 
     Ok(antinodes.len() + coords.len())
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = r#"............
-                      ........0...
-                      .....0......
-                      .......0....
-                      ....0.......
-                      ......A.....
-                      ............
-                      ............
-                      ........A...
-                      .........A..
-                      ............
-                      ............"#;
-        let res = count_antinodes(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 14);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = r#"............
-                      ........0...
-                      .....0......
-                      .......0....
-                      ....0.......
-                      ......A.....
-                      ............
-                      ............
-                      ........A...
-                      .........A..
-                      ............
-                      ............"#;
-
-        let res = count_antinodes_rec(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 34);
-    }
-}
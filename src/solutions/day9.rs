@@ -3,13 +3,14 @@ use itertools::Itertools;
 use std::{
     cmp::Ordering,
     fmt::{self, Display},
-    io,
 };
 
 use tracing::{debug, instrument, trace, Level};
 
 use crate::error::{AppError, Result};
 
+use super::{Answer, Input, Params, Solution};
+
 #[derive(Debug, Clone, Copy)]
 enum Block {
     /// (ID, Len)
@@ -29,7 +30,7 @@ impl Display for Block {
 }
 
 #[derive(Debug)]
-struct Layout {
+pub struct Layout {
     blocks: Vec<Block>,
 }
 
@@ -150,78 +151,56 @@ impl Layout {
     }
 }
 
-pub fn solve(second: bool, line_reader: impl Iterator<Item = io::Result<String>>) -> Result<()> {
-    if second {
-        soft_compress_blocks(line_reader)?;
-    } else {
-        compress_blocks(line_reader)?;
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Parsed = Layout;
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Disk Fragmenter"
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Layout> {
+        Ok(parse(input)?)
+    }
+
+    fn part_one(&self, layout: &Layout, _params: &Params) -> Result<Answer> {
+        Ok(compress_blocks(layout)?.into())
+    }
+
+    fn part_two(&self, layout: &Layout, _params: &Params) -> Result<Answer> {
+        Ok(soft_compress_blocks(layout)?.into())
     }
-    Ok(())
 }
 
-#[instrument(skip_all, ret)]
-fn compress_blocks(
-    line_reader: impl Iterator<Item = io::Result<String>>,
-) -> Result<usize, AppError> {
-    let raw_blocks = line_reader
-        .map_ok(|s| {
-            s.chars()
-                .filter_map(|c| c.to_digit(10).map(|n| n as usize))
-                .collect::<Vec<usize>>()
-        })
-        .collect::<io::Result<Vec<Vec<usize>>>>()
-        .map_err(|e| AppError::DataParse(e.to_string()))?
+#[instrument(skip_all)]
+fn parse(input: Input<'_>) -> Result<Layout, AppError> {
+    let raw_blocks = input
+        .digit_grid()?
         .into_iter()
         .flatten()
+        .map(usize::from)
         .collect_vec();
     trace!(raw_blocks = ?raw_blocks);
     let l: Layout = raw_blocks.into();
     debug!("layout -> {l}");
-    let l = l.optimize_frag();
-    debug!("optimized_layout -> {l}");
-    Ok(l.checksum())
+    Ok(l)
 }
 
 #[instrument(skip_all, ret)]
-fn soft_compress_blocks(
-    line_reader: impl Iterator<Item = io::Result<String>>,
-) -> Result<usize, AppError> {
-    {
-        let raw_blocks = line_reader
-            .map_ok(|s| {
-                s.chars()
-                    .filter_map(|c| c.to_digit(10).map(|n| n as usize))
-                    .collect::<Vec<usize>>()
-            })
-            .collect::<io::Result<Vec<Vec<usize>>>>()
-            .map_err(|e| AppError::DataParse(e.to_string()))?
-            .into_iter()
-            .flatten()
-            .collect_vec();
-        trace!(raw_blocks = ?raw_blocks);
-        let l: Layout = raw_blocks.into();
-        debug!("layout -> {l}");
-        let l = l.optimize_defrag();
-        debug!("optimized_layout -> {l}");
-        Ok(l.checksum())
-    }
+fn compress_blocks(layout: &Layout) -> Result<usize, AppError> {
+    let l = layout.optimize_frag();
+    debug!("optimized_layout -> {l}");
+    Ok(l.checksum())
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn validate_one_star_example() {
-        let data = "2333133121414131402";
-        let res = compress_blocks(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 1928);
-    }
-
-    #[test]
-    fn validate_second_star_example() {
-        let data = "2333133121414131402";
-        let res = soft_compress_blocks(data.lines().map(|s| s.trim().to_string()).map(Ok));
-        assert_eq!(res.unwrap(), 2858);
-    }
+#[instrument(skip_all, ret)]
+fn soft_compress_blocks(layout: &Layout) -> Result<usize, AppError> {
+    let l = layout.optimize_defrag();
+    debug!("optimized_layout -> {l}");
+    Ok(l.checksum())
 }
@@ -0,0 +1,399 @@
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+    ops::Range,
+    str::FromStr,
+};
+
+use tracing::warn;
+
+use crate::error::{AppError, ParseError};
+
+use super::LineReader;
+
+/// Numbered line of the puzzle input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// 1-based line number
+    pub number: usize,
+    pub text: String,
+}
+
+impl Line {
+    /// Parse error pointing at `columns` of this line
+    ///
+    /// `columns` are byte offsets into `text`, as returned by the `str` methods, and are converted
+    /// to the char range [`ParseError::columns`] expects.
+    pub fn error(&self, message: impl Into<String>, columns: Range<usize>) -> AppError {
+        let start = self.text[..columns.start].chars().count();
+        let end = start + self.text[columns].chars().count();
+        AppError::DataParse(ParseError::new(message).at(
+            self.number,
+            self.text.as_str(),
+            start..end,
+        ))
+    }
+
+    /// Parse error pointing at the whole line
+    pub fn line_error(&self, message: impl Into<String>) -> AppError {
+        AppError::DataParse(ParseError::new(message).on_line(self.number, self.text.as_str()))
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    pub fn chars(&self) -> Vec<char> {
+        self.text.chars().collect()
+    }
+
+    /// Every character as a single digit
+    pub fn digits(&self) -> Result<Vec<u8>, AppError> {
+        self.text
+            .char_indices()
+            .map(|(i, ch)| {
+                ch.to_digit(10).map(|n| n as u8).ok_or_else(|| {
+                    self.error(
+                        format!("Expected a digit, got `{ch}`"),
+                        i..i + ch.len_utf8(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Integers separated by whitespace and/or commas
+    pub fn integers<T: FromStr>(&self) -> Result<Vec<T>, AppError> {
+        self.tokens()
+            .map(|(columns, token)| {
+                token
+                    .parse()
+                    .map_err(|_| self.error(format!("Expected an integer, got `{token}`"), columns))
+            })
+            .collect()
+    }
+
+    /// Tokens separated by whitespace and/or commas, with their columns as byte offsets
+    pub fn tokens(&self) -> impl Iterator<Item = (Range<usize>, &str)> {
+        let is_separator = |ch: char| ch.is_whitespace() || ch == ',';
+        let text = self.text.as_str();
+        let mut start = 0;
+        std::iter::from_fn(move || {
+            let token_start = start + text[start..].find(|ch| !is_separator(ch))?;
+            let token_end = text[token_start..]
+                .find(is_separator)
+                .map_or(text.len(), |len| token_start + len);
+            start = token_end;
+            Some((token_start..token_end, &text[token_start..token_end]))
+        })
+    }
+}
+
+/// Splits on `\n` only, unlike [`BufRead::lines`] a `\r` before it stays in the line
+pub fn read_lines<'a>(reader: impl BufRead + 'a) -> LineReader<'a> {
+    Box::new(reader.split(b'\n').map(|line| {
+        String::from_utf8(line?).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }))
+}
+
+/// Fixes made by [`Input`] normalization, logged as a warning once the input is dropped
+#[derive(Debug, Default)]
+struct Fixes {
+    bom: bool,
+    crlf: usize,
+    trailing_whitespace: usize,
+    tabs: usize,
+    trailing_blank: usize,
+}
+
+impl Fixes {
+    fn is_empty(&self) -> bool {
+        !self.bom
+            && self.crlf == 0
+            && self.trailing_whitespace == 0
+            && self.tabs == 0
+            && self.trailing_blank == 0
+    }
+}
+
+/// Puzzle input handed to [`super::Solution::parse`]
+///
+/// Reading is checked, IO errors and invalid UTF-8 become [`AppError::DataParse`] with the line
+/// number instead of being skipped. Unless created with [`Input::raw`], lines are normalized: the
+/// BOM, `\r` line endings and trailing whitespace are stripped, tabs become spaces and trailing
+/// blank lines are dropped.
+pub struct Input<'a> {
+    lines: LineReader<'a>,
+    /// Number of lines read so far
+    read: usize,
+    normalize: bool,
+    fixes: Fixes,
+    /// Normalized lines not returned yet, blank ones wait until a non-blank line follows
+    held: VecDeque<Result<Line, AppError>>,
+    /// Number of blank lines at the end of `held`
+    blanks: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(lines: LineReader<'a>) -> Self {
+        Self {
+            lines,
+            read: 0,
+            normalize: true,
+            fixes: Fixes::default(),
+            held: VecDeque::new(),
+            blanks: 0,
+        }
+    }
+
+    /// Input passed to the solution exactly as read
+    pub fn raw(lines: LineReader<'a>) -> Self {
+        let mut input = Self::new(lines);
+        input.normalize = false;
+        input
+    }
+
+    /// Every line, including blank ones
+    pub fn lines(self) -> Result<Vec<Line>, AppError> {
+        self.collect()
+    }
+
+    /// Lines without the trailing blank ones
+    fn trimmed_lines(self) -> Result<Vec<Line>, AppError> {
+        let mut lines = self.lines()?;
+        while lines.last().is_some_and(Line::is_blank) {
+            lines.pop();
+        }
+        Ok(lines)
+    }
+
+    /// Groups of lines separated by one or more blank lines
+    pub fn blocks(self) -> Result<Vec<Vec<Line>>, AppError> {
+        let mut blocks = vec![];
+        let mut block = vec![];
+        for line in self {
+            let line = line?;
+            if !line.is_blank() {
+                block.push(line);
+            } else if !block.is_empty() {
+                blocks.push(std::mem::take(&mut block));
+            }
+        }
+        if !block.is_empty() {
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    /// Lines of equal length without the trailing blank ones, for grids that need line numbers
+    pub fn rectangular_lines(self) -> Result<Vec<Line>, AppError> {
+        let lines = self.trimmed_lines()?;
+        check_rectangular(&lines)?;
+        Ok(lines)
+    }
+
+    /// Rectangular grid of characters
+    pub fn char_grid(self) -> Result<Vec<Vec<char>>, AppError> {
+        Ok(self.rectangular_lines()?.iter().map(Line::chars).collect())
+    }
+
+    /// Rectangular grid of single digits
+    pub fn digit_grid(self) -> Result<Vec<Vec<u8>>, AppError> {
+        self.rectangular_lines()?.iter().map(Line::digits).collect()
+    }
+
+    /// Lines that are not blank, read errors are kept
+    pub fn non_blank(self) -> impl Iterator<Item = Result<Line, AppError>> + 'a {
+        self.filter(|line| !line.as_ref().is_ok_and(Line::is_blank))
+    }
+
+    /// Integers of every non-blank line, see [`Line::integers`]
+    pub fn integers<T: FromStr>(self) -> Result<Vec<Vec<T>>, AppError> {
+        self.non_blank().map(|line| line?.integers()).collect()
+    }
+}
+
+impl Input<'_> {
+    fn read_line(&mut self) -> Option<Result<Line, AppError>> {
+        let line = self.lines.next()?;
+        self.read += 1;
+        let number = self.read;
+        Some(match line {
+            Ok(text) => Ok(Line { number, text }),
+            Err(err) => Err(AppError::DataParse(ParseError {
+                line: Some(number),
+                ..ParseError::new(err.to_string())
+            })),
+        })
+    }
+
+    fn fix(&mut self, mut line: Line) -> Line {
+        if line.number == 1 {
+            if let Some(text) = line.text.strip_prefix('\u{feff}') {
+                line.text = text.to_string();
+                self.fixes.bom = true;
+            }
+        }
+        if line.text.ends_with('\r') {
+            line.text.pop();
+            self.fixes.crlf += 1;
+        }
+        let trimmed = line.text.trim_end().len();
+        if trimmed < line.text.len() {
+            line.text.truncate(trimmed);
+            self.fixes.trailing_whitespace += 1;
+        }
+        if line.text.contains('\t') {
+            line.text = line.text.replace('\t', " ");
+            self.fixes.tabs += 1;
+        }
+        line
+    }
+}
+
+impl Iterator for Input<'_> {
+    type Item = Result<Line, AppError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.normalize {
+            return self.read_line();
+        }
+        loop {
+            if self.blanks == 0 {
+                if let Some(line) = self.held.pop_front() {
+                    return Some(line);
+                }
+            }
+            let Some(line) = self.read_line() else {
+                // Only blank lines are left
+                self.fixes.trailing_blank += self.held.len();
+                self.held.clear();
+                self.blanks = 0;
+                return None;
+            };
+            let line = line.map(|line| self.fix(line));
+            let blank = line.as_ref().is_ok_and(Line::is_blank);
+            self.held.push_back(line);
+            self.blanks = if blank { self.blanks + 1 } else { 0 };
+        }
+    }
+}
+
+impl Drop for Input<'_> {
+    fn drop(&mut self) {
+        let fixes = &self.fixes;
+        if !fixes.is_empty() {
+            warn!(
+                bom = fixes.bom,
+                crlf_lines = fixes.crlf,
+                trailing_whitespace_lines = fixes.trailing_whitespace,
+                tab_lines = fixes.tabs,
+                trailing_blank_lines = fixes.trailing_blank,
+                "Input normalized, use --raw to read it as is"
+            );
+        }
+    }
+}
+
+fn check_rectangular(lines: &[Line]) -> Result<(), AppError> {
+    let Some(width) = lines.first().map(|line| line.text.chars().count()) else {
+        return Ok(());
+    };
+    match lines.iter().find(|line| line.text.chars().count() != width) {
+        Some(line) => Err(line.line_error(format!(
+            "Expected {width} characters like the first line, got {}",
+            line.text.chars().count()
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn input(text: &'static str) -> Input<'static> {
+        Input::new(read_lines(Cursor::new(text)))
+    }
+
+    fn texts(input: Input<'_>) -> Vec<String> {
+        input.lines().unwrap().into_iter().map(|l| l.text).collect()
+    }
+
+    #[test]
+    fn normalizes_lines() {
+        let text = "\u{feff}3\t4 \r\n\r\n5  6\r\n \r\n\n";
+        assert_eq!(texts(input(text)), ["3 4", "", "5  6"]);
+        assert_eq!(
+            texts(Input::raw(read_lines(Cursor::new(text)))),
+            ["\u{feff}3\t4 \r", "\r", "5  6\r", " \r", ""]
+        );
+        let lines = input("a\n\n\nb\n").lines().unwrap();
+        assert_eq!(
+            lines.iter().map(|l| l.number).collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn splits_blocks_and_integers() {
+        let blocks = input("\n47|53\n97|13\n\n\n75,47, 61\n").blocks().unwrap();
+        let numbers: Vec<Vec<usize>> = blocks[1].iter().map(|l| l.integers().unwrap()).collect();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!((blocks[0][0].number, blocks[1][0].number), (2, 6));
+        assert_eq!(numbers, [[75, 47, 61]]);
+        assert_eq!(
+            input("3   4\n-1 2\n\n").integers::<i32>().unwrap(),
+            [[3, 4], [-1, 2]]
+        );
+    }
+
+    #[test]
+    fn errors_carry_line_context() {
+        let err = input("1 2\n3 x4 5").integers::<u8>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Data File Parsing Error: line 2, columns 3-4: Expected an integer, got `x4`"
+        );
+        let err = input("123\n4a6\n").digit_grid().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Data File Parsing Error: line 2, column 2: Expected a digit, got `a`"
+        );
+        let err = input("1\u{3000}x4\n").integers::<u8>().unwrap_err();
+        assert!(err.to_string().contains("line 1, columns 3-4"), "{err}");
+        let AppError::DataParse(parse) = &err else {
+            panic!("{err:?}");
+        };
+        assert!(parse
+            .snippet()
+            .unwrap()
+            .ends_with("\n  |   ^^ Expected an integer, got `x4`"));
+        let err = input("1é3\n").digit_grid().unwrap_err();
+        assert!(err.to_string().contains("line 1, column 2:"), "{err}");
+        let err = input("..#\n.#\n").char_grid().unwrap_err();
+        assert!(err.to_string().contains("line 2, columns 1-2"), "{err}");
+    }
+
+    #[test]
+    fn rectangular_lines_keep_numbers() {
+        let mut grid = input("header\n\n.#\n^x\n\n");
+        grid.by_ref().take(2).for_each(drop);
+        let lines = grid.rectangular_lines().unwrap();
+        assert_eq!(lines.iter().map(|l| l.number).collect::<Vec<_>>(), [3, 4]);
+        let err = lines[1].error("Unknown character `x`", 1..2);
+        assert!(err.to_string().contains("line 4, column 2"), "{err}");
+    }
+
+    #[test]
+    fn reports_read_errors() {
+        let lines = vec![Ok("ok".to_string()), Err(io::ErrorKind::InvalidData.into())];
+        let err = Input::new(Box::new(lines.into_iter())).lines().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Data File Parsing Error: line 2: invalid data"
+        );
+    }
+}
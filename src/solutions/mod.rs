@@ -1,3 +1,13 @@
+use std::{
+    any::Any,
+    fmt::{self, Display},
+    io,
+    sync::Arc,
+};
+
+use crate::error::{AppError, Result};
+
+pub mod answer;
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -19,5 +29,146 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+pub mod input;
+pub mod params;
 pub mod template;
 pub mod utils;
+
+pub use answer::Answer;
+pub use input::{Input, Line};
+pub use params::{ParamSpec, ParamValue, Params};
+
+/// Lines of the puzzle input
+pub type LineReader<'a> = Box<dyn Iterator<Item = io::Result<String>> + 'a>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Star {
+    One = 1,
+    Two = 2,
+}
+
+impl Star {
+    pub const ALL: [Star; 2] = [Star::One, Star::Two];
+}
+
+impl TryFrom<u8> for Star {
+    type Error = AppError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            n => Err(AppError::DataParse(format!("Unknown star: {n}").into())),
+        }
+    }
+}
+
+impl Display for Star {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", *self as u8)
+    }
+}
+
+/// Single day of the advent calendar
+pub trait Solution: Sync {
+    /// Puzzle input parsed once and shared by both stars
+    type Parsed: Send + Sync + 'static;
+
+    /// Day of the calendar [https://adventofcode.com/2024/day/{day}]
+    fn day(&self) -> u8;
+
+    /// Puzzle title
+    fn title(&self) -> &'static str;
+
+    /// Parameters that can be set with `--param key=value`
+    fn params(&self) -> &'static [ParamSpec] {
+        &[]
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Self::Parsed>;
+
+    /// First star problem
+    fn part_one(&self, parsed: &Self::Parsed, params: &Params) -> Result<Answer>;
+
+    /// Second star problem
+    fn part_two(&self, parsed: &Self::Parsed, params: &Params) -> Result<Answer>;
+}
+
+/// Type erased parsed input of a [`Puzzle`], shared with solutions running on other threads
+pub type Parsed = Arc<dyn Any + Send + Sync>;
+
+/// Object safe view over a [`Solution`], used by the registry
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn params(&self) -> &'static [ParamSpec];
+
+    fn parse(&self, input: Input<'_>) -> Result<Parsed>;
+
+    /// Solves given star, `parsed` must come from [`Puzzle::parse`] of the same puzzle
+    fn solve(&self, star: Star, parsed: &Parsed, params: &Params) -> Result<Answer>;
+}
+
+impl<T: Solution> Puzzle for T {
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solution::title(self)
+    }
+
+    fn params(&self) -> &'static [ParamSpec] {
+        Solution::params(self)
+    }
+
+    fn parse(&self, input: Input<'_>) -> Result<Parsed> {
+        Ok(Arc::new(Solution::parse(self, input)?))
+    }
+
+    fn solve(&self, star: Star, parsed: &Parsed, params: &Params) -> Result<Answer> {
+        let Some(parsed) = parsed.downcast_ref::<T::Parsed>() else {
+            return Err(AppError::Other(format!(
+                "Parsed input does not belong to day {}",
+                Solution::day(self)
+            ))
+            .into());
+        };
+        match star {
+            Star::One => self.part_one(parsed, params),
+            Star::Two => self.part_two(parsed, params),
+        }
+    }
+}
+
+/// Every implemented day, ordered by day number
+pub static REGISTRY: &[&dyn Puzzle] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+];
+
+/// Looks up the solution for a given day
+pub fn find(day: u8) -> Option<&'static dyn Puzzle> {
+    REGISTRY.iter().copied().find(|s| s.day() == day)
+}
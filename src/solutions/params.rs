@@ -0,0 +1,195 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use itertools::Itertools;
+
+use crate::error::AppError;
+
+/// Typed value of a puzzle parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamValue {
+    Unsigned(u64),
+    Signed(i64),
+    Flag(bool),
+}
+
+impl ParamValue {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Unsigned(_) => "an unsigned integer",
+            Self::Signed(_) => "an integer",
+            Self::Flag(_) => "a boolean",
+        }
+    }
+
+    /// Parses `value` into the same type as `self`
+    fn parse_as(&self, value: &str) -> Option<Self> {
+        match self {
+            Self::Unsigned(_) => value.parse().ok().map(Self::Unsigned),
+            Self::Signed(_) => value.parse().ok().map(Self::Signed),
+            Self::Flag(_) => value.parse().ok().map(Self::Flag),
+        }
+    }
+}
+
+impl Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned(n) => write!(f, "{n}"),
+            Self::Signed(n) => write!(f, "{n}"),
+            Self::Flag(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+/// Parameter accepted by a solution, its default also sets the type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub default: ParamValue,
+}
+
+/// Parameters of a solution, defaults overridden by `--param key=value`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<&'static str, ParamValue>);
+
+impl Params {
+    pub fn defaults(specs: &[ParamSpec]) -> Self {
+        Self(specs.iter().map(|spec| (spec.name, spec.default)).collect())
+    }
+
+    /// Applies `overrides` on top of the defaults, unknown and mistyped keys are rejected
+    pub fn resolve(specs: &[ParamSpec], overrides: &[(String, String)]) -> Result<Self, AppError> {
+        let mut params = Self::defaults(specs);
+        if let Some(key) = overrides.iter().map(|(key, _)| key).duplicates().next() {
+            return Err(AppError::InvalidParam(format!(
+                "`{key}` is given more than once"
+            )));
+        }
+        for (key, value) in overrides {
+            let Some(spec) = specs.iter().find(|spec| spec.name == key) else {
+                let expected = if specs.is_empty() {
+                    "this day takes no parameters".to_string()
+                } else {
+                    format!(
+                        "expected one of {}",
+                        specs.iter().map(|s| s.name).join(", ")
+                    )
+                };
+                return Err(AppError::InvalidParam(format!(
+                    "unknown `{key}`, {expected}"
+                )));
+            };
+            let value = spec.default.parse_as(value).ok_or_else(|| {
+                AppError::InvalidParam(format!("`{key}` is {}, got `{value}`", spec.default.kind()))
+            })?;
+            params.0.insert(spec.name, value);
+        }
+        Ok(params)
+    }
+
+    fn get(&self, name: &str) -> Result<ParamValue, AppError> {
+        self.0
+            .get(name)
+            .copied()
+            .ok_or_else(|| AppError::InvalidParam(format!("`{name}` is not declared")))
+    }
+
+    pub fn unsigned(&self, name: &str) -> Result<u64, AppError> {
+        match self.get(name)? {
+            ParamValue::Unsigned(n) => Ok(n),
+            other => Err(mismatch(name, other)),
+        }
+    }
+
+    pub fn signed(&self, name: &str) -> Result<i64, AppError> {
+        match self.get(name)? {
+            ParamValue::Signed(n) => Ok(n),
+            other => Err(mismatch(name, other)),
+        }
+    }
+
+    pub fn flag(&self, name: &str) -> Result<bool, AppError> {
+        match self.get(name)? {
+            ParamValue::Flag(b) => Ok(b),
+            other => Err(mismatch(name, other)),
+        }
+    }
+}
+
+fn mismatch(name: &str, value: ParamValue) -> AppError {
+    AppError::InvalidParam(format!("`{name}` is {}", value.kind()))
+}
+
+/// Splits a `key=value` command line argument
+pub fn parse_key_value(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("expected `key=value`, got `{arg}`")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SPECS: &[ParamSpec] = &[
+        ParamSpec {
+            name: "blinks",
+            help: "Number of blinks",
+            default: ParamValue::Unsigned(25),
+        },
+        ParamSpec {
+            name: "shift",
+            help: "Signed shift",
+            default: ParamValue::Signed(-1),
+        },
+    ];
+
+    fn overrides(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn overrides_defaults() {
+        let params = Params::resolve(SPECS, &overrides(&[("blinks", "500")])).unwrap();
+        assert_eq!(params.unsigned("blinks"), Ok(500));
+        assert_eq!(params.signed("shift"), Ok(-1));
+        assert!(params.flag("blinks").is_err());
+        assert!(params.unsigned("missing").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        for pairs in [
+            &[("blink", "5")][..],
+            &[("blinks", "-5")],
+            &[("shift", "x")],
+            &[("blinks", "1"), ("shift", "1"), ("blinks", "2")],
+        ] {
+            assert!(
+                Params::resolve(SPECS, &overrides(pairs)).is_err(),
+                "{pairs:?}"
+            );
+        }
+        assert!(Params::resolve(&[], &overrides(&[("blinks", "5")])).is_err());
+    }
+
+    #[test]
+    fn parses_key_value() {
+        assert_eq!(
+            parse_key_value("blinks = 500"),
+            Ok(("blinks".into(), "500".into()))
+        );
+        assert!(parse_key_value("blinks").is_err());
+        assert!(parse_key_value("=5").is_err());
+    }
+}
@@ -0,0 +1,27 @@
+//! Example fixtures from `examples/day{N}`, one test per expected answer, see `build.rs`
+
+use std::path::Path;
+
+use advent_2024::{
+    runner::{self, examples},
+    solutions::{self, Params, Star},
+};
+
+fn check(day: u8, star: u8, name: &str) {
+    let puzzle = solutions::find(day).unwrap_or_else(|| panic!("Day {day} is not registered"));
+    let star = Star::try_from(star).unwrap();
+    let examples_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(examples::EXAMPLES_DIR);
+    let example = examples::load(&examples_dir, day)
+        .unwrap()
+        .into_iter()
+        .find(|example| example.name == name)
+        .unwrap_or_else(|| panic!("Day {day} has no example `{name}`"));
+    let params = Params::resolve(puzzle.params(), &example.params).unwrap();
+    let parsed = runner::parse(puzzle, &example.input).unwrap();
+    let answer = runner::solve(puzzle, star, &parsed, &params, None)
+        .answer
+        .unwrap();
+    assert_eq!(answer.to_string(), example.expected[&star]);
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));